@@ -1,14 +1,16 @@
+use std::cell::RefCell;
+use gloo_timers::callback::Timeout;
+use gloo_worker::{Spawnable, WorkerBridge};
 use yew::prelude::*;
-use crate::game::assets::board::Board;
+use crate::controller::com::thinking::{Progress, ThinkRequest};
+use crate::controller::com::worker::{ComInput, ComOutput, ComWorker, WORKER_PATH};
+use crate::controller::game_engine::GameEngine;
+use crate::controller::game_record::GameRecord;
 use crate::game::assets::piece::Piece;
-use crate::game::assets::pieces::Pieces;
-use crate::game::assets::player::Player;
+use crate::game::assets::player::{Player, Players};
 use crate::game::rule::color::Color;
-use crate::game::rule::position::Position;
-use crate::game::events::put_piece::put_piece;
-use crate::game::rule::turn::Turn;
-use crate::game::events::check_puttable_position_exists::check_puttable_position_exists;
-use crate::game::assets::piece::UsedPiece;
+use crate::game::rule::moves::Move;
+use crate::game::rule::rules::Rules;
 use crate::views::game::board_view::BoardViewProps;
 use crate::views::game::piece_view::PieceViewProps;
 use crate::views::game::increment_button::IncrementProps;
@@ -18,129 +20,284 @@ use crate::views::game::board_view::BoardView;
 use crate::views::game::score_view::ScoreView;
 use crate::views::game::decrement_button::DecrementButton;
 use crate::views::game::increment_button::IncrementButton;
+use crate::views::game::result_view::ResultView;
+use crate::views::game::undo_button::{UndoButton, UndoProps};
+use crate::views::game::redo_button::{RedoButton, RedoProps};
+use crate::views::game::record_view::{RecordView, RecordViewProps};
+use crate::views::game::com_controls::{ComControls, ComControlsProps, DEFAULT_DELAY};
 
-#[derive(Debug, Properties, PartialEq, Clone, Copy)]
+#[derive(Debug, Properties, PartialEq, Clone)]
 pub struct GameModeProps{
     pub black: Player,
     pub white: Player,
+    #[prop_or_default]
+    pub rules: Rules,
+}
+impl GameModeProps {
+    pub fn players(&self) -> Players {
+        Players::new(self.black, self.white)
+    }
 }
 
 #[function_component]
 pub fn Game(props: &GameModeProps) -> Html {
-    let turn = use_state(|| Turn{color: Color::Black, player: props.black});
-    let pieces = use_state(|| Pieces::make_pieces());
-    let piece = use_state(|| Piece::new(turn.color, 1));
-    let used_pieces = use_state(|| UsedPiece::new());
-    let position = use_state(|| Position{x: 0, y: 0});
-    let rest_num = use_state(|| pieces.get_rest_num(piece.value, *turn));
-    let board = use_state(|| check_puttable_position_exists(Board::set_initial_state(), *turn));
-
-    let score = board.get_score();
-
-    if (*turn).player == Player::Com{
-        if (*props).black == Player::Com && (*props).white == Player::Com {
-            // TODO: wait until put on a piece
-        }
-        log::info!("com's turn");
-        let pos = board.get_puttable_position();
-        let selected_piece = pieces.select_piece(*turn);
-
-        let squares = board.get_squares();
-        let tmp_board = Board{squares};
-
-        let (new_board, next_turn, new_pieces, new_piece, new_used_pieces) = 
-            put_piece(pos, selected_piece, tmp_board, *turn, (*pieces).clone(), (*used_pieces).clone(), *props);
-        let current_rest_num = new_pieces.get_rest_num(new_piece.value, next_turn);
-        rest_num.set(current_rest_num);
-        turn.set(next_turn);
-        board.set(new_board);
-        piece.set(new_piece);
-        pieces.set(new_pieces);
-        used_pieces.set(new_used_pieces);
-        log::info!("{:?}", selected_piece);
-        log::info!("{:?}", pos);
+    let engine = use_state(|| GameEngine::with_rules(props.players(), props.rules.clone()));
+    let piece = use_state(|| first_piece(&engine, Color::Black));
+    let date = use_state(|| String::from(js_sys::Date::new_0().to_iso_string()).chars().take(10).collect::<String>());
+    let record_error = use_state(|| None::<String>);
+
+    let turn = engine.turn();
+    let rest_num = engine.pieces().get_rest_num(piece.value, turn);
+    let score = engine.board().get_score();
+
+    let paused = use_state(|| false);
+    let delay = use_state(|| DEFAULT_DELAY);
+    let all_com = engine.state().players.black.is_com() && engine.state().players.white.is_com();
+
+    let thinking = use_state(|| None::<Progress>);
+    let answer = use_state(|| None::<(u64, Move)>);
+    // the id of the search whose move is waited for
+    let search_id = use_mut_ref(|| 0_u64);
+
+    // COM thinks in a worker which lives as long as the game, so that leaving the game stops it
+    let worker = {
+        let thinking = thinking.setter();
+        let answer = answer.setter();
+        let search_id = search_id.clone();
+
+        use_memo(move |_| {
+            ComWorker::spawner()
+                .callback(move |output| match output {
+                    ComOutput::Progress { id, progress } if id == *search_id.borrow() => thinking.set(Some(progress)),
+                    ComOutput::Done { id, played } if id == *search_id.borrow() => {
+                        thinking.set(None);
+                        answer.set(Some((id, played)));
+                    },
+                    _ => {},
+                })
+                .spawn(WORKER_PATH)
+        }, ())
+    };
+
+    {
+        let deps = ((*engine).clone(), *paused, *delay);
+        let worker = worker.clone();
+        let search_id = search_id.clone();
+        let thinking = thinking.setter();
+
+        // every new state schedules the next search of COM, and a state changed before the move cancels it
+        use_effect_with_deps(move |(current, paused, delay)| {
+            let timeout = current.think_request().filter(|_| !*paused).map(|request| {
+                let (worker, search_id, thinking) = (worker.clone(), search_id.clone(), thinking.clone());
+                Timeout::new(*delay, move || think(&worker, &search_id, &thinking, request))
+            });
+            move || {
+                drop(timeout);
+                cancel(&worker, &search_id, &thinking);
+            }
+        }, deps);
+    }
+
+    {
+        let engine = engine.clone();
+        let piece = piece.clone();
+        let search_id = search_id.clone();
+
+        use_effect_with_deps(move |answer| {
+            match answer {
+                Some((id, m)) if *id == *search_id.borrow() => play_com_move(&engine, &piece, *m),
+                _ => {},
+            }
+            || ()
+        }, *answer);
+    }
+
+    let on_pause = {
+        let paused = paused.clone();
+
+        Callback::from(move |pause| {
+            paused.set(pause);
+        })
+    };
+
+    let on_step = {
+        let engine = engine.clone();
+        let worker = worker.clone();
+        let search_id = search_id.clone();
+        let thinking = thinking.setter();
+
+        Callback::from(move |_| {
+            if let Some(request) = engine.think_request() {
+                think(&worker, &search_id, &thinking, request);
+            }
+        })
+    };
+
+    let on_delay = {
+        let delay = delay.clone();
+
+        Callback::from(move |current_delay| {
+            delay.set(current_delay);
+        })
+    };
+
+    let com_controls_props = ComControlsProps {
+        paused: *paused,
+        delay: *delay,
+        com_turn: engine.think_request().is_some(),
+        thinking: *thinking,
+        on_pause,
+        on_step,
+        on_delay,
     };
 
     let on_decrement = {
         let piece = piece.clone();
-        let rest_num = rest_num.clone();
-        let pieces = pieces.clone();
-        let turn = turn.clone();
 
         Callback::from(move |current_piece| {
             piece.set(current_piece);
-            let current_rest_num = pieces.get_rest_num(current_piece.value, *turn);
-            rest_num.set(current_rest_num);
         })
     };
 
     let on_increment = {
         let piece = piece.clone();
-        let rest_num = rest_num.clone();
-        let pieces = pieces.clone();
-        let turn = turn.clone();
 
         Callback::from(move |current_piece| {
             piece.set(current_piece);
-            let current_rest_num = pieces.get_rest_num(current_piece.value, *turn);
-            rest_num.set(current_rest_num);
         })
     };
-    
+
 
     let on_put = {
-        let position = position.clone();
-        let board = board.clone();
+        let engine = engine.clone();
         let piece = piece.clone();
-        let turn = turn.clone();
-        let pieces = pieces.clone();
-        let rest_num = rest_num.clone();
-        let used_pieces = used_pieces.clone();
-        let game_mode_props = props.clone();
 
-        match (*turn).player {
+        match turn.player {
             Player::Human => {
                 Callback::from(move |current_position| {
-                    position.set(current_position);
-                    let squares = board.get_squares();
-                    let tmp_board = Board{squares};
-                    if *rest_num > 0 {
-                        let (new_board, next_turn, new_pieces, new_piece, new_used_pieces) = 
-                            put_piece(current_position, *piece, tmp_board, *turn, (*pieces).clone(), (*used_pieces).clone(), game_mode_props);
-                        let current_rest_num = new_pieces.get_rest_num(new_piece.value, next_turn);
-                        rest_num.set(current_rest_num);
-                        turn.set(next_turn);
-                        board.set(new_board);
-                        piece.set(new_piece);
-                        pieces.set(new_pieces);
-                        used_pieces.set(new_used_pieces);
+                    let mut next = (*engine).clone();
+                    let m = Move::put(current_position, *piece);
+                    match next.play(m) {
+                        Ok(()) => {
+                            log::info!("{}\n{}", m, next.board());
+                            piece.set(first_piece(&next, next.turn().color));
+                            engine.set(next);
+                        },
+                        Err(e) => log::info!("{}", e),
                     }
                 })
             },
-            Player::Com => {
+            Player::Com(_) => {
                 Callback::from(move |_| {})
             },
         }
     };
 
+    let values = engine.rules().piece_set.value_range(piece.color).unwrap_or(1..=1);
+
+    let on_undo = {
+        let engine = engine.clone();
+        let piece = piece.clone();
+        let paused = paused.clone();
+
+        Callback::from(move |_| {
+            let mut next = (*engine).clone();
+            if all_com {
+                // COM vs COM is paused to look back one move at a time
+                let _ = next.undo();
+                paused.set(true);
+            } else {
+                // take back the moves of COM too, so that COM does not play again at once
+                while next.undo().is_ok() && next.turn().player.is_com() {}
+            }
+            piece.set(first_piece(&next, next.turn().color));
+            engine.set(next);
+        })
+    };
+
+    let on_redo = {
+        let engine = engine.clone();
+        let piece = piece.clone();
+
+        Callback::from(move |_| {
+            let mut next = (*engine).clone();
+            if all_com {
+                let _ = next.redo();
+            } else {
+                while next.redo().is_ok() && next.turn().player.is_com() {}
+            }
+            piece.set(first_piece(&next, next.turn().color));
+            engine.set(next);
+        })
+    };
+
+    let undo_props = UndoProps {
+        disabled: !engine.can_undo(),
+        on_undo,
+    };
+
+    let redo_props = RedoProps {
+        disabled: !engine.can_redo(),
+        on_redo,
+    };
+
+    let on_load = {
+        let engine = engine.clone();
+        let piece = piece.clone();
+        let date = date.clone();
+        let record_error = record_error.clone();
+
+        Callback::from(move |text: String| {
+            match text.parse::<GameRecord>().and_then(|record| Ok((record.replay()?, record.date))) {
+                Ok((next, played_on)) => {
+                    piece.set(first_piece(&next, next.turn().color));
+                    engine.set(next);
+                    date.set(played_on);
+                    record_error.set(None);
+                },
+                Err(e) => record_error.set(Some(e.to_string())),
+            }
+        })
+    };
+
+    let record_props = RecordViewProps {
+        record: GameRecord::from_engine(&engine, &date).to_string(),
+        error: (*record_error).clone(),
+        on_load,
+    };
+
     let decrement_props = DecrementProps {
         piece: *piece,
+        min_value: *values.start(),
+        max_value: *values.end(),
         on_decrement,
     };
 
     let increment_props = IncrementProps {
         piece: *piece,
+        min_value: *values.start(),
+        max_value: *values.end(),
         on_increment,
     };
 
     let piece_props = PieceViewProps {
-        color: (*piece).color,
-        value: (*piece).value,
-        num: *rest_num,
+        color: piece.color,
+        value: piece.value,
+        num: rest_num,
+    };
+
+    let legal_moves = engine.legal_positions();
+    let previews = match turn.player {
+        Player::Human => legal_moves.positions().into_iter()
+            .map(|position| (position, engine.preview(position, *piece)))
+            .collect(),
+        Player::Com(_) => Default::default(),
     };
 
     let board_props = BoardViewProps {
-        squares: board.squares.clone(),
+        squares: engine.board().squares.clone(),
+        legal_moves,
+        previews,
         on_put,
     };
 
@@ -156,7 +313,53 @@ pub fn Game(props: &GameModeProps) -> Html {
                     <PieceView ..piece_props />
                     <IncrementButton ..increment_props />
                 </div>
+                if engine.rules().takebacks {
+                    <div class="d-flex">
+                        <UndoButton ..undo_props />
+                        <RedoButton ..redo_props />
+                    </div>
+                }
+                if engine.state().players.black.is_com() || engine.state().players.white.is_com() {
+                    <ComControls ..com_controls_props />
+                }
+                if let Some(result) = engine.result() {
+                    <ResultView {result} players={engine.state().players} />
+                }
+                <RecordView ..record_props />
             </main>
         </>
     }
-}
\ No newline at end of file
+}
+
+/// Sends the request to the worker as a new search.
+fn think(worker: &WorkerBridge<ComWorker>, search_id: &RefCell<u64>, thinking: &UseStateSetter<Option<Progress>>, request: ThinkRequest) {
+    let id = search_id.replace_with(|id| *id + 1) + 1;
+    thinking.set(Some(Progress::default()));
+    worker.send(ComInput::Think { id, request: Box::new(request) });
+}
+
+/// Cancels the search in the worker, and ignores its move if it has already been sent.
+fn cancel(worker: &WorkerBridge<ComWorker>, search_id: &RefCell<u64>, thinking: &UseStateSetter<Option<Progress>>) {
+    let id = search_id.replace_with(|id| *id + 1);
+    thinking.set(None);
+    worker.send(ComInput::Cancel { id });
+}
+
+/// Plays the move of COM chosen by the worker.
+fn play_com_move(engine: &UseStateHandle<GameEngine>, piece: &UseStateHandle<Piece>, m: Move) {
+    let mut next = (**engine).clone();
+    match next.play(m) {
+        Ok(()) => {
+            log::info!("{}\n{}", m, next.board());
+            piece.set(first_piece(&next, next.turn().color));
+            engine.set(next);
+        },
+        Err(e) => log::error!("{}", e),
+    }
+}
+
+/// Returns the piece with the smallest value of the color, which is selected when the turn starts.
+fn first_piece(engine: &GameEngine, color: Color) -> Piece {
+    let value = engine.rules().piece_set.value_range(color).map_or(1, |values| *values.start());
+    Piece::new(color, value)
+}
@@ -2,7 +2,8 @@ use yew::prelude::*;
 use yew::{Callback, function_component, html, Html, Properties};
 
 use crate::app::game::GameModeProps;
-use crate::game::assets::player::Player;
+use crate::game::assets::{board::SIZES, level::Level, piece_set::PieceSet, player::Player};
+use crate::game::rule::{flip_rule::BuiltinFlipRule, rules::Rules};
 
 use super::game::Game;
 
@@ -16,6 +17,7 @@ pub struct PlayerProps {
 pub fn menu_page() -> Html {
     let player_black = use_state(|| Player::Human);
     let player_white = use_state(|| Player::Human);
+    let rules = use_state(Rules::default);
 
     let is_start = use_state(|| false);
 
@@ -24,40 +26,159 @@ pub fn menu_page() -> Html {
 
     let game_mode = GameModeProps {
         black: *black,
-        white: *white
+        white: *white,
+        rules: (*rules).clone(),
     };
 
     let select_human_black_side = {
         let player_black = player_black.clone();
-        Callback::from(move |e| {
+        Callback::from(move |_| {
             player_black.set(Player::Human);
         })
     };
 
     let select_com_black_side = {
         let player_black = player_black.clone();
-        Callback::from(move |e| {
-            player_black.set(Player::Com);
+        Callback::from(move |_| {
+            player_black.set(Player::Com(Level::default().config()));
         })
     };
 
     let select_human_white_side = {
         let player_white = player_white.clone();
-        Callback::from(move |e| {
+        Callback::from(move |_| {
             player_white.set(Player::Human);
         })
     };
 
     let select_com_white_side = {
         let player_white = player_white.clone();
-        Callback::from(move |e: MouseEvent| {
-            player_white.set(Player::Com);
+        Callback::from(move |_: MouseEvent| {
+            player_white.set(Player::Com(Level::default().config()));
+        })
+    };
+
+    let levels = |player: &UseStateHandle<Player>, side: &'static str| -> Html {
+        let Player::Com(config) = **player else {
+            return html! {};
+        };
+        Level::ALL.iter().map(|level| {
+            let level = *level;
+            let select_level = {
+                let player = player.clone();
+                Callback::from(move |_: MouseEvent| {
+                    player.set(Player::Com(level.config()));
+                })
+            };
+            let id = format!("{}{}", side, level.name());
+            html! {
+                <>
+                    <input 
+                        type="radio" 
+                        id={id.clone()} 
+                        name={format!("{}Level", side)} 
+                        value={level.name()} 
+                        checked={Level::from_config(config) == Some(level)}
+                        onclick={select_level}
+                    />
+                    <label for={id}>{level.name()}</label>
+                </>
+            }
+        }).collect::<Html>()
+    };
+    let black_levels = levels(&player_black, "black");
+    let white_levels = levels(&player_white, "white");
+
+    let board_sizes = SIZES.iter().map(|size| {
+        let select_board_size = {
+            let rules = rules.clone();
+            let size = *size;
+            Callback::from(move |_: MouseEvent| {
+                let mut next = (*rules).clone();
+                next.board_size = size;
+                rules.set(next);
+            })
+        };
+        let id = format!("size{}", size);
+        html! {
+            <>
+                <input 
+                    type="radio" 
+                    id={id.clone()} 
+                    name="boardSize" 
+                    value={size.to_string()} 
+                    checked={rules.board_size == *size}
+                    onclick={select_board_size}
+                />
+                <label for={id}>{format!("{0}x{0}", size)}</label>
+            </>
+        }
+    }).collect::<Html>();
+
+    let piece_sets = PieceSet::presets().into_iter().enumerate().map(|(i, (name, piece_set))| {
+        let checked = rules.piece_set == piece_set;
+        let select_piece_set = {
+            let rules = rules.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut next = (*rules).clone();
+                next.piece_set = piece_set.clone();
+                rules.set(next);
+            })
+        };
+        let id = format!("pieceSet{}", i);
+        html! {
+            <>
+                <input 
+                    type="radio" 
+                    id={id.clone()} 
+                    name="pieceSet" 
+                    value={name} 
+                    {checked}
+                    onclick={select_piece_set}
+                />
+                <label for={id}>{name}</label>
+            </>
+        }
+    }).collect::<Html>();
+
+    let flip_rules = BuiltinFlipRule::ALL.iter().map(|flip_rule| {
+        let flip_rule = *flip_rule;
+        let select_flip_rule = {
+            let rules = rules.clone();
+            Callback::from(move |_: MouseEvent| {
+                let mut next = (*rules).clone();
+                next.flip_rule = flip_rule;
+                rules.set(next);
+            })
+        };
+        let id = format!("{:?}", flip_rule);
+        html! {
+            <>
+                <input 
+                    type="radio" 
+                    id={id.clone()} 
+                    name="flipRule" 
+                    value={id.clone()} 
+                    checked={rules.flip_rule == flip_rule}
+                    onclick={select_flip_rule}
+                />
+                <label for={id}>{flip_rule.name()}</label>
+            </>
+        }
+    }).collect::<Html>();
+
+    let toggle_takebacks = {
+        let rules = rules.clone();
+        Callback::from(move |_: MouseEvent| {
+            let mut next = (*rules).clone();
+            next.takebacks = !next.takebacks;
+            rules.set(next);
         })
     };
 
     let game_start = {
         let is_start = is_start.clone();
-        Callback::from(move |e: MouseEvent| {
+        Callback::from(move |_: MouseEvent| {
             is_start.set(true);
         })
     };
@@ -86,6 +207,7 @@ pub fn menu_page() -> Html {
                             onclick={select_com_black_side}
                         />
                         <label for="vs">{"COM"}</label>
+                        <div>{black_levels}</div>
                     </fieldset>
                 </form>
                 <form>
@@ -107,11 +229,41 @@ pub fn menu_page() -> Html {
                             onclick={select_com_white_side}
                         />
                         <label for="vs">{"COM"}</label>
+                        <div>{white_levels}</div>
+                    </fieldset>
+                </form>
+                <form>
+                    <fieldset>
+                        <legend>{"Board Size"}</legend>
+                        {board_sizes}
+                    </fieldset>
+                </form>
+                <form>
+                    <fieldset>
+                        <legend>{"Pieces"}</legend>
+                        {piece_sets}
+                    </fieldset>
+                </form>
+                <form>
+                    <fieldset>
+                        <legend>{"Flip Rule"}</legend>
+                        {flip_rules}
+                    </fieldset>
+                </form>
+                <form>
+                    <fieldset>
+                        <legend>{"Rated"}</legend>
+                        <input 
+                            type="checkbox" 
+                            id="rated" 
+                            name="rated" 
+                            checked={!rules.takebacks}
+                            onclick={toggle_takebacks}
+                        />
+                        <label for="rated">{"Rated game (no takebacks)"}</label>
                     </fieldset>
                 </form>
                 <button onclick={game_start}>{"Game Start!"}</button>
-                <div>{"Bug Info: You cannot start Com vs Com."}</div>
-                <div>{"バグ情報: 現在 Com vs Com で遊ぶことはできません"}</div>
             </>
         }
     }
@@ -0,0 +1,6 @@
+use gloo_worker::Registrable;
+use number_reversi::controller::com::worker::ComWorker;
+
+fn main() {
+    ComWorker::registrar().register();
+}
@@ -0,0 +1,23 @@
+use std::env;
+
+use number_reversi::controller::com::opening_book::{BookGenerator, MIN_GAMES};
+use number_reversi::game::assets::strategy_config::StrategyConfig;
+use number_reversi::game::rule::rules::Rules;
+
+/// Learns the opening book of the default rules from self-play and prints its JSON.
+///
+/// Usage: `make_opening_book [games] [plies] [min_games] [seed] > assets/opening_book.json`
+fn main() {
+    let args = env::args().skip(1).map(|arg| arg.parse::<u64>().expect("arguments are numbers")).collect::<Vec<_>>();
+    let arg = |i: usize, default: u64| args.get(i).copied().unwrap_or(default);
+    let generator = BookGenerator {
+        games: arg(0, 20_000) as usize,
+        plies: arg(1, 3) as usize,
+        min_games: arg(2, MIN_GAMES as u64) as u32,
+        opening: StrategyConfig::AlphaBeta { depth: 2, node_budget: Some(2_000) },
+        exploration: 0.1,
+        strategy: StrategyConfig::AlphaBeta { depth: 1, node_budget: None },
+        seed: arg(3, 0),
+    };
+    println!("{}", generator.generate(Rules::default()).to_json());
+}
@@ -1 +1,5 @@
-pub mod game_state;
\ No newline at end of file
+pub mod game_state;
+pub mod game_engine;
+pub mod history;pub mod game_record;
+pub mod snapshot;
+pub mod com;
@@ -0,0 +1,14 @@
+pub mod strategy;
+pub mod random;
+pub mod greedy;
+pub mod evaluation;
+pub mod alpha_beta;
+pub mod mcts;
+pub mod thinking;
+pub mod worker;
+pub mod zobrist;
+pub mod bit_state;
+pub mod search_state;
+pub mod transposition;
+pub mod endgame;
+pub mod opening_book;
\ No newline at end of file
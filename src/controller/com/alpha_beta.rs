@@ -0,0 +1,488 @@
+use crate::{
+    controller::game_state::GameState,
+    game::rule::moves::Move
+};
+
+use super::{
+    endgame::EndgameSolver, evaluation::{Evaluation, WIN}, search_state::SearchState, strategy::Strategy,
+    transposition::{TranspositionTable, DEFAULT_CAPACITY}
+};
+
+/// The default number of moves `AlphaBeta` looks ahead.
+pub const DEFAULT_DEPTH: usize = 3;
+
+/// `AlphaBeta` is the COM which searches the moves ahead with negamax and alpha-beta pruning.
+///
+/// Every puttable square is tried with every value the player still has, and the positions
+/// at the end of the search are valued by the `Evaluation`.
+/// The moves are played on a `SearchState`, so the standard board is searched on a `BitState`.
+/// The search deepens one move at a time up to `depth`, and stops early when it has played
+/// `node_budget` moves, returning the best move of the deepest finished search.
+/// The values of the positions searched are kept in a `TranspositionTable` of `table_size` entries,
+/// so a position reached again by another order of moves is not searched again.
+/// When few squares are left, the `EndgameSolver` is tried first, and its perfect move is played
+/// if it solves the game within its own node budget. The moves played by a solver which gives up
+/// count against the `node_budget` of the search, and an `AlphaBeta` made from a `StrategyConfig`
+/// gives the solver half of it, so trying the solver costs no more than the budget.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::{alpha_beta::AlphaBeta, strategy::Strategy}, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+/// let mut com = AlphaBeta::new(2).with_node_budget(2_000);
+/// let m = com.choose_move(&state);
+/// assert!(state.legal_moves().contains(&m));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct AlphaBeta {
+    /// The number of moves to look ahead.
+    pub depth: usize,
+    /// The number of moves played in the search before it stops, or `None` for no limit.
+    pub node_budget: Option<usize>,
+    /// The number of entries of the transposition table, or 0 for no table.
+    pub table_size: usize,
+    /// The solver tried before the search, or `None` to always search.
+    pub endgame: Option<EndgameSolver>,
+    pub evaluation: Evaluation,
+}
+impl Default for AlphaBeta {
+    fn default() -> Self {
+        Self::new(DEFAULT_DEPTH)
+    }
+}
+impl AlphaBeta {
+    /// Creates an `AlphaBeta` which looks `depth` moves ahead with no node budget,
+    /// a transposition table of the default size and the default `EndgameSolver`.
+    pub fn new(depth: usize) -> Self {
+        Self {
+            depth: depth.max(1),
+            node_budget: None,
+            table_size: DEFAULT_CAPACITY,
+            endgame: Some(EndgameSolver::default()),
+            evaluation: Evaluation::default(),
+        }
+    }
+
+    /// Creates the `AlphaBeta` of `StrategyConfig::AlphaBeta`, whose `EndgameSolver` gives up
+    /// within half of the `node_budget`, leaving the rest to the search.
+    pub fn from_config(depth: usize, node_budget: Option<usize>) -> Self {
+        match node_budget {
+            Some(node_budget) => Self::new(depth)
+                .with_node_budget(node_budget)
+                .with_endgame(Some(EndgameSolver { node_budget: Some(node_budget / 2), ..EndgameSolver::default() })),
+            None => Self::new(depth),
+        }
+    }
+
+    /// Limits the number of moves played in the search.
+    pub fn with_node_budget(self, node_budget: usize) -> Self {
+        Self { node_budget: Some(node_budget), ..self }
+    }
+
+    /// Replaces the number of entries of the transposition table. 0 searches without a table.
+    pub fn with_table_size(self, table_size: usize) -> Self {
+        Self { table_size, ..self }
+    }
+
+    /// Replaces the solver tried before the search. `None` always searches.
+    pub fn with_endgame(self, endgame: Option<EndgameSolver>) -> Self {
+        Self { endgame, ..self }
+    }
+
+    /// Searches the `GameState` and returns the best move with its value,
+    /// or `None` if the game is over.
+    pub fn search(&self, state: &GameState) -> Option<SearchResult> {
+        let mut deepening = Deepening::new(*self, state.clone());
+        while deepening.deepen() {}
+        deepening.result()
+    }
+}
+
+/// `Deepening` is a search by `AlphaBeta` which is deepened one move at a time,
+/// so that it can be stopped between the depths.
+/// Deepening it to the end gives the same result as `AlphaBeta::search`.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::alpha_beta::{AlphaBeta, Deepening}, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+/// let mut deepening = Deepening::new(AlphaBeta::new(2), state.clone());
+/// assert!(deepening.deepen());
+/// assert_eq!(deepening.result().unwrap().depth, 1);
+/// assert!(!deepening.deepen());
+/// assert_eq!(deepening.result(), AlphaBeta::new(2).search(&state));
+/// ```
+pub struct Deepening {
+    search: Search,
+    state: SearchState,
+    hash: u64,
+    result: Option<SearchResult>,
+    /// The last depth searched, even if the search of it was stopped.
+    depth: usize,
+    finished: bool,
+}
+impl Deepening {
+    pub fn new(config: AlphaBeta, state: GameState) -> Self {
+        let table = (config.table_size > 0).then(|| TranspositionTable::new(config.table_size));
+        let search = Search { config, table, nodes: 0, table_hits: 0, aborted: false };
+        let state = SearchState::from(&state);
+        Self { search, hash: state.hash(), state, result: None, depth: 0, finished: false }
+    }
+
+    /// Searches one move deeper than before, and returns whether it can be deepened more.
+    pub fn deepen(&mut self) -> bool {
+        if self.finished {
+            return false;
+        }
+        if self.depth == 0 && self.solve() {
+            self.finished = true;
+            return false;
+        }
+        self.depth += 1;
+        let previous = self.result.map(|result| result.best);
+        match self.search.root(&self.state, self.hash, self.depth, previous) {
+            // the result of a depth stopped by the budget is used only when no depth has finished
+            Some((best, value)) if !self.search.aborted || self.result.is_none() => {
+                let (nodes, table_hits) = (self.search.nodes, self.search.table_hits);
+                self.result = Some(SearchResult { best, value, depth: self.depth, nodes, table_hits });
+            },
+            _ => {},
+        }
+        self.finished = self.search.aborted || self.result.is_none() || self.depth >= self.search.config.depth;
+        !self.finished
+    }
+
+    /// Solves the game with the `EndgameSolver` if few squares are left, and returns whether it is solved.
+    /// The moves played by a solver which gives up are counted in the moves of the search.
+    fn solve(&mut self) -> bool {
+        let Some(solver) = self.search.config.endgame.filter(|solver| self.state.empties() <= solver.empties) else {
+            return false;
+        };
+        let solution = match solver.solve_search_state(&self.state) {
+            Ok(solution) => solution,
+            Err(gave_up) => {
+                self.search.nodes = gave_up.nodes;
+                return false;
+            },
+        };
+        let Some(best) = solution.line.first().copied() else {
+            return false;
+        };
+        let value = WIN * solution.value.signum() + solution.value;
+        self.search.nodes = solution.nodes;
+        self.result = Some(SearchResult { best, value, depth: solution.line.len(), nodes: solution.nodes, table_hits: 0 });
+        true
+    }
+
+    /// Returns the result of the deepest finished search, or `None` if the game is over.
+    pub fn result(&self) -> Option<SearchResult> {
+        self.result.map(|result| SearchResult { nodes: self.search.nodes, table_hits: self.search.table_hits, ..result })
+    }
+
+    /// Returns the number of depths searched so far.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+}
+
+impl Strategy for AlphaBeta {
+    fn choose_move(&mut self, state: &GameState) -> Move {
+        self.search(state).map_or(Move::Pass, |result| result.best)
+    }
+}
+
+/// The result of a search by `AlphaBeta`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchResult {
+    pub best: Move,
+    /// The value of the best move for the player of the searched state.
+    pub value: i64,
+    /// The depth of the deepest finished search, or the length of the perfect line if the game was solved.
+    pub depth: usize,
+    /// The number of moves played in the whole search.
+    pub nodes: usize,
+    /// The number of positions whose value was taken from the transposition table.
+    pub table_hits: usize,
+}
+
+struct Search {
+    config: AlphaBeta,
+    table: Option<TranspositionTable<Entry>>,
+    nodes: usize,
+    table_hits: usize,
+    aborted: bool,
+}
+
+/// The value of a position kept in the transposition table.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    depth: usize,
+    value: i64,
+    bound: Bound,
+    best: Option<Move>,
+}
+
+/// What the value of an `Entry` is, since a cut off search only finds a bound of the value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+impl Search {
+    /// Searches the moves of the root with `previous`, the best move of the last depth, first.
+    /// Returns the best move found so far even if the budget runs out.
+    fn root(&mut self, state: &SearchState, hash: u64, depth: usize, previous: Option<Move>) -> Option<(Move, i64)> {
+        let mut children = self.children(state, hash);
+        move_to_front(&mut children, previous);
+        let mut best: Option<(Move, i64)> = None;
+        let mut alpha = -i64::MAX;
+        for (m, next, next_hash) in children {
+            let value = self.child_value(state, &next, next_hash, depth, alpha, i64::MAX);
+            if self.aborted {
+                return best.or(Some((m, value)));
+            }
+            if best.is_none_or(|(_, best)| value > best) {
+                best = Some((m, value));
+                alpha = alpha.max(value);
+            }
+        }
+        best
+    }
+
+    fn negamax(&mut self, state: &SearchState, hash: u64, depth: usize, mut alpha: i64, mut beta: i64) -> i64 {
+        let color = state.color();
+        if depth == 0 || state.is_over() {
+            return self.config.evaluation.evaluate(state, color);
+        }
+        let original_alpha = alpha;
+        let entry = self.table.as_ref().and_then(|table| table.get(hash)).copied();
+        if let Some(entry) = entry.filter(|entry| entry.depth >= depth) {
+            match entry.bound {
+                Bound::Exact => alpha = beta,
+                Bound::Lower => alpha = alpha.max(entry.value),
+                Bound::Upper => beta = beta.min(entry.value),
+            }
+            if alpha >= beta {
+                self.table_hits += 1;
+                return entry.value;
+            }
+        }
+
+        let mut children = self.children(state, hash);
+        if self.aborted {
+            return -i64::MAX;
+        }
+        move_to_front(&mut children, entry.and_then(|entry| entry.best));
+        let (mut best, mut best_move) = (-i64::MAX, None);
+        for (m, next, next_hash) in children {
+            let value = self.child_value(state, &next, next_hash, depth, alpha, beta);
+            if self.aborted {
+                return best;
+            }
+            if value > best {
+                (best, best_move) = (value, Some(m));
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best <= original_alpha {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.store(hash, Entry { depth, value: best, bound, best: best_move });
+        best
+    }
+
+    /// Returns the value of `next` for the player of `state`.
+    /// The player may move again when the opponent has to pass, so the sign is kept in that case.
+    fn child_value(&mut self, state: &SearchState, next: &SearchState, hash: u64, depth: usize, alpha: i64, beta: i64) -> i64 {
+        if next.is_over() {
+            self.config.evaluation.evaluate(next, state.color())
+        } else if next.color() == state.color() {
+            self.negamax(next, hash, depth - 1, alpha, beta)
+        } else {
+            -self.negamax(next, hash, depth - 1, -beta, -alpha)
+        }
+    }
+
+    /// Plays every legal move, and returns the states after them and their hashes with the most promising first.
+    fn children(&mut self, state: &SearchState, hash: u64) -> Vec<(Move, SearchState, u64)> {
+        let color = state.color();
+        let mut children = vec![];
+        for m in state.legal_moves() {
+            if self.config.node_budget.is_some_and(|budget| self.nodes >= budget) {
+                self.aborted = true;
+                break;
+            }
+            self.nodes += 1;
+            if let Ok(next) = state.play(m) {
+                let next_hash = state.update_hash(hash, m, &next);
+                children.push((m, next, next_hash));
+            }
+        }
+        children.sort_by_key(|(_, next, _)| -next.score_difference(color));
+        children
+    }
+
+    /// Keeps the entry unless the table has a deeper search of the same position.
+    fn store(&mut self, hash: u64, entry: Entry) {
+        if let Some(table) = &mut self.table {
+            if table.get(hash).is_none_or(|old| old.depth <= entry.depth) {
+                table.insert(hash, entry);
+            }
+        }
+    }
+}
+
+/// Moves the child of the move to the front, to search it first.
+fn move_to_front(children: &mut Vec<(Move, SearchState, u64)>, m: Option<Move>) {
+    if let Some(i) = children.iter().position(|(child, _, _)| Some(*child) == m) {
+        let child = children.remove(i);
+        children.insert(0, child);
+    }
+}
+
+
+#[cfg(test)]
+mod alpha_beta_test {
+    use crate::{
+        controller::{
+            com::{alpha_beta::{AlphaBeta, Deepening, Search}, endgame::EndgameSolver, evaluation::WIN, search_state::SearchState, transposition::TranspositionTable},
+            game_state::GameState
+        },
+        game::{
+            assets::{board::Board, piece::Piece, player::{Player, Players}},
+            rule::{color::Color, moves::Move, position::Position, rules::Rules}
+        }
+    };
+
+    fn com_to_move(board: &str) -> GameState {
+        GameState {
+            board: board.parse::<Board>().unwrap(),
+            ..GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 4, ..Rules::default() })
+        }
+    }
+
+    #[test]
+    fn when_game_can_be_won_at_once_then_the_winning_move_is_found() {
+        // a 10 on a4 fills the board and reverses the w10, while any other value loses
+        let state = com_to_move("
+             w1  w1  w1  w1
+             b1  w1  w1  w1
+            w10  w1  w1  w1
+              .  w1  w1  b1
+        ");
+        let result = AlphaBeta::new(2).search(&state).unwrap();
+        assert_eq!(result.best, Move::put(Position{x: 0, y: 3}, Piece::new(Color::Black, 10)));
+        assert!(result.value > WIN);
+    }
+
+    #[test]
+    fn when_few_squares_are_left_then_the_game_is_solved() {
+        let state = com_to_move("
+             .  w1  w1  w1
+            b1  w1  w1  w1
+            w1  w1  w2  w1
+             .  w1  w1  b9
+        ");
+        let solution = EndgameSolver::default().solve(&state).unwrap();
+        let solved = AlphaBeta::new(1).search(&state).unwrap();
+        assert_eq!(solved.best, solution.line[0]);
+        assert_eq!(solved.value, WIN * solution.value.signum() + solution.value);
+        assert_eq!(solved.depth, solution.line.len());
+
+        let searched = AlphaBeta::new(1).with_endgame(None).search(&state).unwrap();
+        assert_eq!(searched.depth, 1);
+    }
+
+    #[test]
+    fn when_endgame_solver_gives_up_then_its_moves_count_against_the_node_budget() {
+        let state = com_to_move("
+             .  w1  w1  w1
+            b1  w1  w1  w1
+            w1  w1  w2  w1
+             .  w1  w1  b9
+        ");
+        let config = AlphaBeta::from_config(3, Some(8));
+        assert_eq!(config.endgame.unwrap().node_budget, Some(4));
+        assert_eq!(config.endgame.unwrap().solve(&state), None);
+
+        let mut deepening = Deepening::new(config, state);
+        while deepening.deepen() {}
+        let result = deepening.result().unwrap();
+        assert!((4..=8).contains(&result.nodes), "{}", result.nodes);
+    }
+
+    #[test]
+    fn when_depth_is_larger_then_the_same_state_is_searched_deeper() {
+        let state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 6, ..Rules::default() });
+        let shallow = AlphaBeta::new(1).search(&state).unwrap();
+        let deep = AlphaBeta::new(2).search(&state).unwrap();
+        assert_eq!(shallow.depth, 1);
+        assert_eq!(deep.depth, 2);
+        assert!(deep.nodes > shallow.nodes);
+    }
+
+    #[test]
+    fn when_node_budget_runs_out_then_the_last_finished_depth_is_used() {
+        let state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        let result = AlphaBeta::new(4).with_node_budget(100).search(&state).unwrap();
+        assert_eq!(result.depth, 1);
+        assert!(result.nodes <= 100);
+        assert!(state.legal_moves().contains(&result.best));
+
+        // even a budget too small for one depth gives a legal move
+        let result = AlphaBeta::new(4).with_node_budget(3).search(&state).unwrap();
+        assert!(state.legal_moves().contains(&result.best));
+    }
+
+    #[test]
+    fn when_node_budget_runs_out_before_any_child_then_nothing_is_stored() {
+        let state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        let config = AlphaBeta::new(2).with_node_budget(0);
+        let mut search = Search { config, table: Some(TranspositionTable::new(16)), nodes: 0, table_hits: 0, aborted: false };
+        let state = SearchState::from(&state);
+        search.negamax(&state, state.hash(), 2, -i64::MAX, i64::MAX);
+        assert!(search.aborted);
+        assert!(search.table.unwrap().is_empty());
+    }
+
+    #[test]
+    fn when_transposition_table_is_used_then_the_value_is_the_same_with_fewer_nodes() {
+        // the same position is reached by putting two pieces of Black in either order
+        let mut state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 4, ..Rules::default() });
+        state.pieces.black.retain(|v, _| *v <= 2);
+        state.pieces.white.retain(|v, _| *v <= 2);
+        let with_table = AlphaBeta::new(4).search(&state).unwrap();
+        let without_table = AlphaBeta::new(4).with_table_size(0).search(&state).unwrap();
+        assert_eq!(with_table.value, without_table.value);
+        assert!(with_table.table_hits > 0);
+        assert_eq!(without_table.table_hits, 0);
+        assert!(with_table.nodes < without_table.nodes);
+    }
+
+    #[test]
+    fn when_game_is_over_then_no_move_is_chosen() {
+        let state = com_to_move("
+            b1  .  .  .
+             .  .  .  .
+             .  .  .  .
+             .  .  . w1
+        ");
+        assert_eq!(AlphaBeta::default().search(&state), None);
+    }
+}
@@ -0,0 +1,318 @@
+//! `BitState` is a `Copy` form of a `GameState` on the standard 8x8 board, which the COMs search.
+//!
+//! The board is a `BitBoard`, the remaining pieces are counted in arrays indexed by the value,
+//! and the used pieces are masks with the bit of each value set, so playing a move copies
+//! the state without any heap allocation. A move is played by the same rules as `GameState::play`,
+//! including the pass which follows a put and its penalty.
+//! Only a `GameState` on the 8x8 board whose values are at most `MAX_VALUE` can be converted.
+//!
+//! # Examples
+//!
+//! ```
+//! use number_reversi::controller::{com::bit_state::BitState, game_state::GameState};
+//! use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+//! use number_reversi::game::rule::{color::Color, moves::Move, position::Position};
+//!
+//! let state = GameState::new(Players::new(Player::Human, Player::Human));
+//! let bits = BitState::try_from(&state).unwrap();
+//! assert_eq!(bits.legal_moves(), state.legal_moves());
+//!
+//! let m = Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 3));
+//! assert_eq!(bits.play(m), Ok(BitState::try_from(&state.play(m).unwrap()).unwrap()));
+//! ```
+
+use std::{error::Error, fmt};
+
+use crate::{
+    controller::game_state::GameState,
+    game::{
+        assets::{bit_board::{bits, BitBoard, UnsupportedSize}, piece::Piece, square::Square},
+        rule::{
+            color::Color, flip_rule::BuiltinFlipRule, moves::{Move, MoveError},
+            result::{EndReason, GameResult}, score::Score
+        }
+    }
+};
+
+/// The largest value of a piece a `BitState` can hold.
+pub const MAX_VALUE: u8 = 15;
+const VALUES: usize = MAX_VALUE as usize + 1;
+const SIZE: usize = 8;
+/// The four corners of the board.
+const CORNERS: u64 = 0x8100_0000_0000_0081;
+/// The squares on the edges of the board, except the corners.
+const EDGES: u64 = 0x7e81_8181_8181_817e;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitState {
+    pub board: BitBoard,
+    /// The numbers of the remaining pieces of black and white, indexed by the value.
+    pub pieces: [[u8; VALUES]; 2],
+    /// The used pieces of black and white, as masks with the bit of each used value set.
+    pub used: [u16; 2],
+    /// The color of the player to move.
+    pub color: Color,
+    pub flip_rule: BuiltinFlipRule,
+}
+impl BitState {
+    /// Returns the numbers of the remaining pieces of the color, indexed by the value.
+    pub fn pieces(&self, color: Color) -> &[u8; VALUES] {
+        &self.pieces[index(color)]
+    }
+
+    /// Returns the used pieces of the color, as a mask with the bit of each used value set.
+    pub fn used(&self, color: Color) -> u16 {
+        self.used[index(color)]
+    }
+
+    /// Returns whether no piece of any value is left for the color.
+    pub fn is_exhausted(&self, color: Color) -> bool {
+        self.pieces(color).iter().all(|n| *n == 0)
+    }
+
+    /// Returns whether the player of the color can put a piece.
+    pub fn can_move(&self, color: Color) -> bool {
+        self.board.legal_moves(color) != 0 && !self.is_exhausted(color)
+    }
+
+    /// Returns why the game has ended, in the same order as `check_game_over`, or `None` if it has not.
+    pub fn end_reason(&self) -> Option<EndReason> {
+        let opponent = self.color.opponent();
+        if self.board.occupied() == u64::MAX {
+            Some(EndReason::BoardFull)
+        } else if let Some(exhausted) = [self.color, opponent].into_iter().find(|color| self.is_exhausted(*color)) {
+            Some(EndReason::PiecesExhausted(exhausted))
+        } else if !self.can_move(self.color) && !self.can_move(opponent) {
+            Some(EndReason::NoMoves)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the `GameResult` if the game has ended, otherwise `None`.
+    pub fn result(&self) -> Option<GameResult> {
+        self.end_reason().map(|reason| GameResult::new(self.board.score(), reason))
+    }
+
+    /// Returns whether the game has ended.
+    pub fn is_over(&self) -> bool {
+        self.end_reason().is_some()
+    }
+
+    /// Returns every `Move` the player to move can make, in the same order as `GameState::legal_moves`.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.is_over() {
+            return vec![];
+        }
+        let mut positions = BitBoard::positions(self.board.legal_moves(self.color));
+        positions.sort();
+        let values = (0..=MAX_VALUE).filter(|v| self.pieces(self.color)[*v as usize] > 0).collect::<Vec<u8>>();
+        let moves = positions.into_iter()
+            .flat_map(|position| values.iter().map(move |v| Move::put(position, Piece::new(self.color, *v))))
+            .collect::<Vec<Move>>();
+        if moves.is_empty() {
+            vec![Move::Pass]
+        } else {
+            moves
+        }
+    }
+
+    /// Returns the `BitState` after the `Move` is played, as `GameState::play` does.
+    ///
+    /// # Errors
+    ///
+    /// Returns the same `MoveError` as `GameState::play` if the `Move` is rejected.
+    pub fn play(&self, m: Move) -> Result<Self, MoveError> {
+        if self.is_over() {
+            return Err(MoveError::GameOver);
+        }
+        let (position, piece) = match m {
+            Move::Put { position, piece } => (position, piece),
+            Move::Pass if self.board.legal_moves(self.color) == 0 => return Ok(self.check_pass()),
+            Move::Pass => return Err(MoveError::PassNotAllowed),
+        };
+        if piece.color != self.color {
+            return Err(MoveError::WrongTurn(piece.color));
+        }
+        if position.x >= SIZE || position.y >= SIZE {
+            return Err(MoveError::OutOfBounds(position));
+        }
+        if self.board.legal_moves(self.color) & BitBoard::bit(position) == 0 {
+            return Err(MoveError::SquareNotPuttable(position));
+        }
+        if piece.value > MAX_VALUE || self.pieces(self.color)[piece.value as usize] == 0 {
+            return Err(MoveError::NoPiecesOfValue(piece));
+        }
+        let mut next = *self;
+        next.pieces[index(self.color)][piece.value as usize] -= 1;
+        next.used[index(self.color)] |= 1 << piece.value;
+        next.board = self.board.put(position, piece, &self.flip_rule);
+        next.color = self.color.opponent();
+        Ok(next.check_pass())
+    }
+
+    /// Passes the turn as `check_pass` does, giving the opponent the largest piece they have used back.
+    fn check_pass(&self) -> Self {
+        let next = self.color.opponent();
+        if self.can_move(self.color) || !self.can_move(next) || self.is_exhausted(self.color) {
+            return *self;
+        }
+        let mut state = Self { color: next, ..*self };
+        let used = self.used[index(next)];
+        if used != 0 {
+            let value = 15 - used.leading_zeros() as usize;
+            state.used[index(next)] &= !(1 << value);
+            state.pieces[index(next)][value] += 1;
+        }
+        state
+    }
+
+    /// Returns the `Score` of the black and white pieces.
+    pub fn score(&self) -> Score {
+        self.board.score()
+    }
+
+    /// Returns the number of empty squares of the board.
+    pub fn empties(&self) -> usize {
+        self.board.occupied().count_zeros() as usize
+    }
+
+    /// Returns the number of the squares where the color can put a piece.
+    pub fn mobility(&self, color: Color) -> usize {
+        self.board.legal_moves(color).count_ones() as usize
+    }
+
+    /// Returns the differences of the values of the pieces of the color on the corners
+    /// and on the other squares of the edges.
+    pub fn stability(&self, color: Color) -> (i64, i64) {
+        let (own, opponent) = match color {
+            Color::Black => (self.board.black, self.board.white),
+            Color::White => (self.board.white, self.board.black),
+        };
+        let sum = |mask: u64| bits(mask).map(|i| self.board.values[i] as i64).sum::<i64>();
+        (sum(own & CORNERS) - sum(opponent & CORNERS), sum(own & EDGES) - sum(opponent & EDGES))
+    }
+
+    /// Returns the total value of the remaining pieces of the color.
+    pub fn inventory(&self, color: Color) -> i64 {
+        self.pieces(color).iter().enumerate().map(|(v, n)| v as i64 * *n as i64).sum()
+    }
+}
+
+/// The error returned when a `GameState` cannot be represented as a `BitState`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsupportedState {
+    /// The board is not 8x8.
+    Size(UnsupportedSize),
+    /// A piece has a value larger than `MAX_VALUE`, the largest of which is kept.
+    Value(u8),
+}
+impl fmt::Display for UnsupportedState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UnsupportedState::Size(e) => write!(f, "{}", e),
+            UnsupportedState::Value(value) => write!(f, "a piece of the value {} cannot be represented as a bit state", value),
+        }
+    }
+}
+impl Error for UnsupportedState {}
+
+impl TryFrom<&GameState> for BitState {
+    type Error = UnsupportedState;
+
+    fn try_from(state: &GameState) -> Result<Self, Self::Error> {
+        let board = BitBoard::try_from(&state.board).map_err(UnsupportedState::Size)?;
+        let on_board = state.board.squares.iter().flatten().filter_map(|square| match square {
+            Square::Put(piece) => Some(piece.value),
+            Square::Empty => None,
+        });
+        let remaining = state.pieces.black.keys().chain(state.pieces.white.keys()).copied();
+        let used = state.used.piece_set.iter().map(|piece| piece.value);
+        if let Some(value) = on_board.chain(remaining).chain(used).filter(|value| *value > MAX_VALUE).max() {
+            return Err(UnsupportedState::Value(value));
+        }
+        let mut bit_state = BitState {
+            board,
+            pieces: [[0; VALUES]; 2],
+            used: [0; 2],
+            color: state.turn.color,
+            flip_rule: state.rules.flip_rule,
+        };
+        for (color, pieces) in [(Color::Black, &state.pieces.black), (Color::White, &state.pieces.white)] {
+            for (v, n) in pieces {
+                bit_state.pieces[index(color)][*v as usize] = *n;
+            }
+        }
+        for piece in &state.used.piece_set {
+            bit_state.used[index(piece.color)] |= 1 << piece.value;
+        }
+        Ok(bit_state)
+    }
+}
+
+fn index(color: Color) -> usize {
+    match color {
+        Color::Black => 0,
+        Color::White => 1,
+    }
+}
+
+
+#[cfg(test)]
+mod bit_state_test {
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        controller::{com::bit_state::{BitState, UnsupportedState}, game_state::GameState},
+        game::{
+            assets::{bit_board::UnsupportedSize, piece::Piece, piece_set::PieceSet, player::{Player, Players}},
+            rule::{color::Color, flip_rule::BuiltinFlipRule, moves::Move, position::Position, rules::Rules}
+        }
+    };
+
+    #[test]
+    fn when_games_are_played_then_every_state_matches_the_game_state() {
+        for (seed, flip_rule) in BuiltinFlipRule::ALL.into_iter().enumerate() {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed as u64);
+            let rules = Rules { flip_rule, ..Rules::default() };
+            let mut state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), rules);
+            let mut bits = BitState::try_from(&state).unwrap();
+            loop {
+                assert_eq!(bits.legal_moves(), state.legal_moves());
+                assert_eq!(bits.result(), state.result());
+                assert_eq!(bits.play(Move::Pass).is_ok(), state.play(Move::Pass).is_ok());
+                let Some(m) = state.legal_moves().choose(&mut rng).copied() else {
+                    break;
+                };
+                state = state.play(m).unwrap();
+                bits = bits.play(m).unwrap();
+                assert_eq!(bits, BitState::try_from(&state).unwrap(), "{}\n{}", m, state.board);
+            }
+        }
+    }
+
+    #[test]
+    fn when_move_is_rejected_then_the_error_is_the_same_as_the_game_state() {
+        let state = GameState::new(Players::new(Player::Human, Player::Human));
+        let bits = BitState::try_from(&state).unwrap();
+        for m in [
+            Move::put(Position{x: 4, y: 2}, Piece::new(Color::White, 3)),
+            Move::put(Position{x: 8, y: 2}, Piece::new(Color::Black, 3)),
+            Move::put(Position{x: 0, y: 0}, Piece::new(Color::Black, 3)),
+            Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 11)),
+            Move::Pass,
+        ] {
+            assert_eq!(bits.play(m).err(), state.play(m).err(), "{}", m);
+        }
+    }
+
+    #[test]
+    fn when_state_does_not_fit_then_it_cannot_be_converted() {
+        let small = GameState::with_rules(Players::new(Player::Human, Player::Human), Rules { board_size: 6, ..Rules::default() });
+        assert_eq!(BitState::try_from(&small), Err(UnsupportedState::Size(UnsupportedSize(6))));
+        let large = Rules { piece_set: PieceSet::uniform(1..=20, 1), ..Rules::default() };
+        let large = GameState::with_rules(Players::new(Player::Human, Player::Human), large);
+        assert_eq!(BitState::try_from(&large), Err(UnsupportedState::Value(20)));
+    }
+}
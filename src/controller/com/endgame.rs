@@ -0,0 +1,321 @@
+use crate::{
+    controller::game_state::GameState,
+    game::{assets::square::Square, rule::{moves::Move, score::Score}}
+};
+
+use super::{search_state::SearchState, transposition::{TranspositionTable, DEFAULT_CAPACITY}};
+
+/// The default number of empty squares from which `AlphaBeta` tries `EndgameSolver`.
+pub const DEFAULT_EMPTIES: usize = 10;
+/// The default number of moves `EndgameSolver` plays before it gives up.
+pub const DEFAULT_NODE_BUDGET: usize = 50_000;
+
+/// `EndgameSolver` searches every move to the end of the game, and finds the exact final score
+/// difference under perfect play of both players and the line of moves which leads to it.
+///
+/// The penalties of passes are played as in the game, so they are part of the result.
+/// The game is played on a `SearchState`, so the standard board is solved on a `BitState`.
+/// The moves which leave the opponent the fewest squares are searched first, the others are only
+/// tested to be worse than the first with a null window, and the searched positions are kept
+/// in a `TranspositionTable` of `table_size` entries.
+/// The search gives up when it has played `node_budget` moves, since the values of the pieces
+/// make the tree too large to solve until only a few squares are left.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::endgame::EndgameSolver, game_state::GameState};
+/// use number_reversi::game::assets::{board::Board, player::{Player, Players}};
+/// use number_reversi::game::rule::rules::Rules;
+///
+/// let state = GameState {
+///     board: "
+///          w1  w1  w1  w1
+///          b1  w1  w1  w1
+///         w10  w1  w1  w1
+///           .  w1  w1  b1
+///     ".parse::<Board>().unwrap(),
+///     ..GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 4, ..Rules::default() })
+/// };
+/// let solution = EndgameSolver::default().solve(&state).unwrap();
+/// assert_eq!(solution.line[0].to_string(), "B10a4");
+/// assert!(solution.value > 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EndgameSolver {
+    /// The largest number of empty squares for which the solver is tried by `AlphaBeta`.
+    pub empties: usize,
+    /// The number of moves played in the search before it gives up, or `None` for no limit.
+    pub node_budget: Option<usize>,
+    /// The number of entries of the transposition table, or 0 for no table.
+    pub table_size: usize,
+}
+impl Default for EndgameSolver {
+    fn default() -> Self {
+        Self { empties: DEFAULT_EMPTIES, node_budget: Some(DEFAULT_NODE_BUDGET), table_size: DEFAULT_CAPACITY }
+    }
+}
+impl EndgameSolver {
+    /// Returns whether the `GameState` has few enough empty squares to try the solver.
+    pub fn can_solve(&self, state: &GameState) -> bool {
+        empties(state) <= self.empties
+    }
+
+    /// Solves the `GameState`, or returns `None` if the node budget runs out.
+    /// A finished game is solved with an empty line.
+    pub fn solve(&self, state: &GameState) -> Option<Solution> {
+        self.solve_search_state(&SearchState::from(state)).ok()
+    }
+
+    /// Solves the `SearchState`, the same as `solve` of the `GameState` it was made from,
+    /// or returns `GaveUp` with the number of moves played if the node budget runs out.
+    pub fn solve_search_state(&self, state: &SearchState) -> Result<Solution, GaveUp> {
+        let table = (self.table_size > 0).then(|| TranspositionTable::new(self.table_size));
+        let mut search = Search { config: *self, table, nodes: 0, aborted: false };
+        let hash = state.hash();
+        let value = search.value(state, hash, -i64::MAX, i64::MAX);
+        let gave_up = GaveUp { nodes: search.nodes };
+        if search.aborted {
+            return Err(gave_up);
+        }
+        let (line, score) = search.line(state, hash, value).ok_or(gave_up)?;
+        Ok(Solution { value, line, score, nodes: search.nodes })
+    }
+}
+
+/// The result of `EndgameSolver::solve_search_state` when it gives up.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GaveUp {
+    /// The number of moves played in the search before it gave up.
+    pub nodes: usize,
+}
+
+/// The result of `EndgameSolver::solve`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// The final score difference for the player of the solved state.
+    pub value: i64,
+    /// The moves of both players under perfect play to the end of the game.
+    /// A pass forced after a put is not written, since it is played with the put.
+    pub line: Vec<Move>,
+    /// The final score after the line.
+    pub score: Score,
+    /// The number of moves played in the search.
+    pub nodes: usize,
+}
+
+/// Returns the number of empty squares of the board.
+pub fn empties(state: &GameState) -> usize {
+    state.board.squares.iter().flatten().filter(|square| **square == Square::Empty).count()
+}
+
+struct Search {
+    config: EndgameSolver,
+    table: Option<TranspositionTable<Entry>>,
+    nodes: usize,
+    aborted: bool,
+}
+
+/// The value of a solved position kept in the transposition table.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    value: i64,
+    bound: Bound,
+    best: Option<Move>,
+}
+
+/// What the value of an `Entry` is, since a cut off search only finds a bound of the value.
+#[derive(Debug, Clone, Copy)]
+enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+impl Search {
+    /// Returns the final score difference for the player of `state` under perfect play,
+    /// or a bound of it outside the window of `alpha` and `beta`.
+    fn value(&mut self, state: &SearchState, hash: u64, mut alpha: i64, mut beta: i64) -> i64 {
+        if state.is_over() {
+            return state.score_difference(state.color());
+        }
+        let original_alpha = alpha;
+        let entry = self.table.as_ref().and_then(|table| table.get(hash)).copied();
+        if let Some(entry) = entry {
+            match entry.bound {
+                Bound::Exact => return entry.value,
+                Bound::Lower => alpha = alpha.max(entry.value),
+                Bound::Upper => beta = beta.min(entry.value),
+            }
+            if alpha >= beta {
+                return entry.value;
+            }
+        }
+
+        let (mut best, mut best_move) = (-i64::MAX, None);
+        for (i, (m, next, next_hash)) in self.children(state, hash, entry.and_then(|entry| entry.best)).into_iter().enumerate() {
+            // the moves after the first are only tested to be worse, and searched again if they are not
+            let mut value = match i {
+                0 => self.child_value(state, &next, next_hash, alpha, beta),
+                _ => self.child_value(state, &next, next_hash, alpha, alpha + 1),
+            };
+            if i > 0 && value > alpha && value < beta && !self.aborted {
+                value = self.child_value(state, &next, next_hash, alpha, beta);
+            }
+            if self.aborted {
+                return best;
+            }
+            if value > best {
+                (best, best_move) = (value, Some(m));
+            }
+            alpha = alpha.max(value);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best <= original_alpha {
+            Bound::Upper
+        } else if best >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        if let Some(table) = &mut self.table {
+            table.insert(hash, Entry { value: best, bound, best: best_move });
+        }
+        best
+    }
+
+    /// Returns the value of `next` for the player of `state`.
+    /// The player may move again when the opponent has to pass, so the sign is kept in that case.
+    fn child_value(&mut self, state: &SearchState, next: &SearchState, hash: u64, alpha: i64, beta: i64) -> i64 {
+        if next.is_over() {
+            next.score_difference(state.color())
+        } else if next.color() == state.color() {
+            self.value(next, hash, alpha, beta)
+        } else {
+            -self.value(next, hash, -beta, -alpha)
+        }
+    }
+
+    /// Follows the moves which keep the value from `state` to the end of the game,
+    /// and returns them with the final score, or `None` if the budget runs out or no move keeps the value.
+    fn line(&mut self, state: &SearchState, hash: u64, value: i64) -> Option<(Vec<Move>, Score)> {
+        let (mut state, mut hash, mut value) = (state.clone(), hash, value);
+        let mut line = vec![];
+        while !state.is_over() {
+            let mut found = None;
+            for (m, next, next_hash) in self.children(&state, hash, None) {
+                // a window around the value tells whether the child keeps it exactly
+                let child = self.child_value(&state, &next, next_hash, value - 1, value + 1);
+                if self.aborted {
+                    return None;
+                }
+                if child == value {
+                    found = Some((m, next, next_hash));
+                    break;
+                }
+            }
+            // a collision of hashes in the table can leave no move with the value, and then it is not solved
+            let (m, next, next_hash) = found?;
+            line.push(m);
+            if next.color() != state.color() {
+                value = -value;
+            }
+            (state, hash) = (next, next_hash);
+        }
+        Some((line, state.score()))
+    }
+
+    /// Plays every legal move, and returns the states after them and their hashes,
+    /// with `first` first and then the moves which leave the opponent the fewest squares.
+    fn children(&mut self, state: &SearchState, hash: u64, first: Option<Move>) -> Vec<(Move, SearchState, u64)> {
+        let color = state.color();
+        let mut children = vec![];
+        for m in state.legal_moves() {
+            if self.config.node_budget.is_some_and(|budget| self.nodes >= budget) {
+                self.aborted = true;
+                break;
+            }
+            self.nodes += 1;
+            if let Ok(next) = state.play(m) {
+                let next_hash = state.update_hash(hash, m, &next);
+                children.push((m, next, next_hash));
+            }
+        }
+        children.sort_by_cached_key(|(m, next, _)| {
+            let mobility = match next.color() == color {
+                true => 0,
+                false => next.mobility(next.color()),
+            };
+            (Some(*m) != first, mobility, -next.score_difference(color))
+        });
+        children
+    }
+}
+
+
+#[cfg(test)]
+mod endgame_test {
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        controller::{com::{endgame::{empties, EndgameSolver}, greedy::score_difference}, game_state::GameState},
+        game::{assets::player::{Player, Players}, rule::rules::Rules}
+    };
+
+    /// Returns the final score difference under perfect play by trying every move.
+    fn minimax(state: &GameState) -> i64 {
+        if state.is_over() {
+            return score_difference(&state.board, state.turn.color);
+        }
+        state.legal_moves().into_iter().map(|m| {
+            let next = state.play(m).unwrap();
+            match next.is_over() {
+                true => score_difference(&next.board, state.turn.color),
+                false if next.turn.color == state.turn.color => minimax(&next),
+                false => -minimax(&next),
+            }
+        }).max().unwrap()
+    }
+
+    /// Plays random moves with only two values each on a small board until few squares are left.
+    fn endgames() -> Vec<GameState> {
+        (0..12).filter_map(|seed| {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 4, ..Rules::default() });
+            state.pieces.black.retain(|v, _| *v <= 2);
+            state.pieces.white.retain(|v, _| *v <= 2);
+            while empties(&state) > 5 {
+                state = state.play(*state.legal_moves().choose(&mut rng)?).unwrap();
+            }
+            Some(state)
+        })
+        .collect()
+    }
+
+    #[test]
+    fn when_endgame_is_solved_then_the_value_is_exact_and_the_line_reaches_it() {
+        let endgames = endgames();
+        assert!(endgames.len() > 5);
+        for state in endgames {
+            let solution = EndgameSolver { node_budget: None, ..EndgameSolver::default() }.solve(&state).unwrap();
+            assert_eq!(solution.value, minimax(&state), "\n{}", state.board);
+
+            let last = solution.line.iter().fold(state.clone(), |state, m| state.play(*m).unwrap());
+            assert!(last.is_over());
+            assert_eq!(score_difference(&last.board, state.turn.color), solution.value);
+            assert_eq!(last.board.get_score(), solution.score);
+        }
+    }
+
+    #[test]
+    fn when_node_budget_runs_out_then_it_is_not_solved() {
+        let state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 4, ..Rules::default() });
+        let solver = EndgameSolver { node_budget: Some(100), ..EndgameSolver::default() };
+        assert!(!solver.can_solve(&state));
+        assert_eq!(solver.solve(&state), None);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::game::rule::color::Color;
+
+use super::search_state::SearchState;
+
+/// `Evaluation` estimates how good a `GameState` is for one of the players.
+///
+/// Every term is the value of the player minus the value of the opponent, multiplied by its weight:
+///
+/// * `score` - the score on the board
+/// * `mobility` - the number of puttable squares
+/// * `corner` - the values of the pieces on the corners, which can never be reversed
+/// * `edge` - the values of the pieces on the other squares of the edges, which are hard to reverse
+/// * `inventory` - the total value of the remaining pieces
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::{evaluation::Evaluation, search_state::SearchState}, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+/// use number_reversi::game::rule::color::Color;
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+/// assert_eq!(Evaluation::default().evaluate(&SearchState::from(&state), Color::Black), 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Evaluation {
+    pub score: i64,
+    pub mobility: i64,
+    pub corner: i64,
+    pub edge: i64,
+    pub inventory: i64,
+}
+impl Default for Evaluation {
+    fn default() -> Self {
+        Self { score: 10, mobility: 4, corner: 15, edge: 4, inventory: 3 }
+    }
+}
+impl Evaluation {
+    /// Returns the value of the `SearchState` for the `color`.
+    /// A finished game is valued by its final score alone, far beyond any unfinished game.
+    pub fn evaluate(&self, state: &SearchState, color: Color) -> i64 {
+        let difference = state.score_difference(color);
+        let opponent = color.opponent();
+        let (own, other) = (state.mobility(color), state.mobility(opponent));
+        // the same conditions as `check_game_over`, with the mobilities counted once for both
+        if state.is_full() || state.is_exhausted(color) || state.is_exhausted(opponent) || (own == 0 && other == 0) {
+            return WIN * difference.signum() + difference;
+        }
+        let (corner, edge) = state.stability(color);
+        self.score * difference
+            + self.mobility * (own as i64 - other as i64)
+            + self.corner * corner
+            + self.edge * edge
+            + self.inventory * (state.inventory(color) - state.inventory(opponent))
+    }
+}
+
+/// The value of a won game before its score difference is added.
+/// Larger than any evaluation of an unfinished game.
+pub const WIN: i64 = 1 << 40;
+
+
+#[cfg(test)]
+mod evaluation_test {
+    use crate::{
+        controller::{com::{evaluation::{Evaluation, WIN}, search_state::SearchState}, game_state::GameState},
+        game::{assets::{board::Board, player::{Player, Players}}, rule::color::Color}
+    };
+
+    fn state(board: &str) -> SearchState {
+        SearchState::from(&GameState {
+            board: board.parse::<Board>().unwrap(),
+            ..GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM))
+        })
+    }
+
+    #[test]
+    fn when_piece_is_on_corner_then_it_is_valued_more_than_on_edge() {
+        let corner = state("
+            b5  .  .  .
+             .  b1 w1 .
+             .  w1 b1 .
+             .  .  .  .
+        ");
+        let edge = state("
+             .  b5  .  .
+             .  b1 w1 .
+             .  w1 b1 .
+             .  .  .  .
+        ");
+        let evaluation = Evaluation { mobility: 0, ..Evaluation::default() };
+        assert!(evaluation.evaluate(&corner, Color::Black) > evaluation.evaluate(&edge, Color::Black));
+        assert_eq!(evaluation.evaluate(&corner, Color::Black), -evaluation.evaluate(&corner, Color::White));
+    }
+
+    #[test]
+    fn when_game_is_over_then_only_the_final_score_counts() {
+        let state = state("
+            b1  .  .  .
+             .  .  .  .
+             .  .  .  .
+             .  .  . w3
+        ");
+        assert!(state.is_over());
+        assert_eq!(Evaluation::default().evaluate(&state, Color::White), WIN + 2);
+        assert_eq!(Evaluation::default().evaluate(&state, Color::Black), -WIN - 2);
+    }
+}
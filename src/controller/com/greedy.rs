@@ -0,0 +1,127 @@
+use crate::{
+    controller::game_state::GameState,
+    game::{assets::board::Board, rule::{color::Color, moves::Move}}
+};
+
+use super::{search_state::SearchState, strategy::Strategy};
+
+/// `Greedy` is the COM which looks one move ahead.
+///
+/// It tries every puttable square with every value it still has, and chooses the move
+/// which leaves the largest score difference on the board.
+/// When several moves are equally good, the first of them in `GameState::legal_moves` is chosen.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::greedy::Greedy, game_state::GameState};
+/// use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+/// use number_reversi::game::rule::{color::Color, moves::Move};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+/// // every first move reverses a 1, so the largest piece gains the most
+/// match Greedy.best_move(&state) {
+///     Some(Move::Put { piece, .. }) => assert_eq!(piece, Piece::new(Color::Black, 10)),
+///     m => panic!("unexpected move: {:?}", m),
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Greedy;
+impl Greedy {
+    /// Returns the best `Move` for the player of the current turn, or `None` if the game is over.
+    pub fn best_move(&self, state: &GameState) -> Option<Move> {
+        self.best_search_move(&SearchState::from(state))
+    }
+
+    /// Returns the best `Move` of the `SearchState`, the same as `best_move` of the `GameState` it was made from.
+    pub fn best_search_move(&self, state: &SearchState) -> Option<Move> {
+        let color = state.color();
+        state.legal_moves().into_iter()
+            .filter_map(|m| state.play(m).ok().map(|next| (m, next.score_difference(color))))
+            // max_by_key keeps the last of equal moves, so the moves are reversed to keep the first
+            .rev()
+            .max_by_key(|(_, difference)| *difference)
+            .map(|(m, _)| m)
+    }
+}
+impl Strategy for Greedy {
+    fn choose_move(&mut self, state: &GameState) -> Move {
+        self.best_move(state).unwrap_or(Move::Pass)
+    }
+}
+
+/// Returns the score of the `color` minus the score of its opponent.
+pub fn score_difference(board: &Board, color: Color) -> i64 {
+    let score = board.get_score();
+    let (black, white) = (score.black as i64, score.white as i64);
+    match color {
+        Color::Black => black - white,
+        Color::White => white - black,
+    }
+}
+
+
+#[cfg(test)]
+mod greedy_test {
+    use crate::{
+        controller::{com::greedy::Greedy, game_state::GameState},
+        game::{
+            assets::{board::Board, piece::Piece, player::{Player, Players}},
+            rule::{color::Color, moves::Move, position::Position}
+        }
+    };
+
+    fn com_to_move(board: &str) -> GameState {
+        GameState {
+            board: board.parse::<Board>().unwrap(),
+            ..GameState::new(Players::new(Player::RANDOM_COM, Player::Human))
+        }
+    }
+
+    #[test]
+    fn when_large_piece_can_be_reversed_then_it_is_taken() {
+        // a1 reverses the w9 with a 9 or 10, which gains more than any other square
+        let state = com_to_move("
+                a   b   c   d   e   f   g   h
+            1   .  w9  b1   .   .   .   .   .
+            2   .   .   .   .   .   .   .   .
+            3   .   .   .   .   .   .   .   .
+            4   .   .   .  b1  w1   .   .   .
+            5   .   .   .  w1  b1   .   .   .
+            6   .   .   .   .   .   .   .   .
+            7   .   .   .   .   .   .   .   .
+            8   .   .   .   .   .   .   .   .
+        ");
+        assert_eq!(Greedy.best_move(&state), Some(Move::put(Position{x: 0, y: 0}, Piece::new(Color::Black, 10))));
+    }
+
+    #[test]
+    fn when_large_piece_is_used_up_then_next_largest_is_chosen() {
+        let mut state = com_to_move(&Board::set_initial_state().to_string());
+        state.pieces.black.insert(10, 0);
+        match Greedy.best_move(&state) {
+            Some(Move::Put { piece, .. }) => assert_eq!(piece, Piece::new(Color::Black, 9)),
+            m => panic!("unexpected move: {:?}", m),
+        }
+    }
+
+    #[test]
+    fn when_only_pass_is_legal_then_passes() {
+        let state = com_to_move("
+                a   b   c   d
+            1   .   .  w1   .
+            2   .  b1   .   .
+            3   .   .   .   .
+            4   .   .   .   .
+        ");
+        assert_eq!(Greedy.best_move(&state), Some(Move::Pass));
+
+        let state = com_to_move("
+            b1  .  .  .
+             .  .  .  .
+             .  .  .  .
+             .  .  . w1
+        ");
+        assert_eq!(Greedy.best_move(&state), None);
+    }
+}
@@ -0,0 +1,320 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::{
+    controller::game_state::GameState,
+    game::rule::{color::Color, moves::Move}
+};
+
+use super::{greedy::Greedy, search_state::SearchState, strategy::Strategy};
+
+/// The default number of iterations of `Mcts`.
+pub const DEFAULT_ITERATIONS: usize = 1_000;
+
+/// How `Mcts` plays a game to the end from a new node of the tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Rollout {
+    /// Every move is chosen at random from the legal moves.
+    #[default]
+    Random,
+    /// Every move is chosen by `Greedy`. Stronger, but much slower than `Random`.
+    Greedy,
+}
+
+/// `Mcts` is the COM which searches with Monte Carlo tree search.
+///
+/// Each iteration walks down the tree choosing moves by UCT, adds one new move to the tree,
+/// plays the game to the end by the `Rollout`, and counts the result on the way back.
+/// The move tried the most times at the root is chosen, so the COM plays better the more
+/// iterations it is given. The random choices are drawn from `seed`, so the same state
+/// always gives the same move. The games are played on a `SearchState`, so a rollout on
+/// the standard board copies a `BitState` instead of the whole `GameState`.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::{mcts::Mcts, strategy::Strategy}, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+/// let mut com = Mcts::new(50, 42);
+/// let m = com.choose_move(&state);
+/// assert!(state.legal_moves().contains(&m));
+/// assert_eq!(com.choose_move(&state), m);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mcts {
+    pub iterations: usize,
+    /// The constant of UCT which weighs trying rare moves against repeating good ones.
+    pub exploration: f64,
+    pub rollout: Rollout,
+    pub seed: u64,
+}
+impl Default for Mcts {
+    fn default() -> Self {
+        Self::new(DEFAULT_ITERATIONS, 0)
+    }
+}
+impl Mcts {
+    /// Creates an `Mcts` which runs `iterations` times with random rollouts.
+    pub fn new(iterations: usize, seed: u64) -> Self {
+        Self { iterations: iterations.max(1), exploration: std::f64::consts::SQRT_2, rollout: Rollout::Random, seed }
+    }
+
+    /// Replaces the `Rollout` which plays the games to the end.
+    pub fn with_rollout(self, rollout: Rollout) -> Self {
+        Self { rollout, ..self }
+    }
+
+    /// Replaces the constant of UCT. The larger it is, the more the rarely tried moves are tried.
+    pub fn with_exploration(self, exploration: f64) -> Self {
+        Self { exploration, ..self }
+    }
+
+    /// Searches the `GameState` and returns the most tried move, or `None` if the game is over.
+    pub fn search(&self, state: &GameState) -> Option<MctsResult> {
+        let mut tree = Tree::new(*self, state.clone())?;
+        tree.grow(self.iterations);
+        tree.result()
+    }
+}
+
+/// `Tree` is the tree of a search by `Mcts`, which can be grown a number of iterations at a time,
+/// so that the search can be stopped between them.
+/// Growing it by `Mcts::iterations` gives the same result as `Mcts::search`.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::mcts::{Mcts, Tree}, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+/// let mut tree = Tree::new(Mcts::new(30, 7), state.clone()).unwrap();
+/// assert_eq!(tree.grow(10), 10);
+/// assert_eq!(tree.grow(100), 20);
+/// assert_eq!(tree.result(), Mcts::new(30, 7).search(&state));
+/// ```
+pub struct Tree {
+    config: Mcts,
+    nodes: Vec<Node>,
+    rng: ChaCha8Rng,
+    iterations: usize,
+}
+impl Tree {
+    /// Creates the tree of the `GameState`, or returns `None` if the game is over.
+    pub fn new(config: Mcts, state: GameState) -> Option<Self> {
+        if state.legal_moves().is_empty() {
+            return None;
+        }
+        let state = SearchState::from(&state);
+        let mover = state.color().opponent();
+        Some(Self {
+            config,
+            nodes: vec![Node::new(state, None, None, mover)],
+            rng: ChaCha8Rng::seed_from_u64(config.seed),
+            iterations: 0,
+        })
+    }
+
+    /// Runs at most `iterations` more iterations without exceeding `Mcts::iterations`,
+    /// and returns the number of iterations run.
+    pub fn grow(&mut self, iterations: usize) -> usize {
+        let iterations = iterations.min(self.config.iterations - self.iterations);
+        for _ in 0..iterations {
+            let leaf = self.select();
+            let node = self.expand(leaf);
+            let winner = self.rollout(node);
+            backpropagate(&mut self.nodes, node, winner);
+        }
+        self.iterations += iterations;
+        iterations
+    }
+
+    /// Returns the number of iterations run so far.
+    pub fn iterations(&self) -> usize {
+        self.iterations
+    }
+
+    /// Returns the most tried move so far, or `None` if no iteration has been run.
+    pub fn result(&self) -> Option<MctsResult> {
+        self.nodes[0].children.iter()
+            .map(|child| &self.nodes[*child])
+            .max_by_key(|child| child.visits)
+            .and_then(|child| Some(MctsResult {
+                best: child.played?,
+                visits: child.visits,
+                win_rate: child.wins / child.visits as f64,
+            }))
+    }
+
+    /// Walks down from the root by UCT to a node which still has untried moves or ends the game.
+    fn select(&self) -> usize {
+        let tree = &self.nodes;
+        let mut index = 0;
+        while tree[index].untried.is_empty() && !tree[index].children.is_empty() {
+            let parent = &tree[index];
+            let log_visits = (parent.visits as f64).ln();
+            index = *parent.children.iter()
+                .max_by(|a, b| {
+                    let (a, b) = (self.uct(&tree[**a], log_visits), self.uct(&tree[**b], log_visits));
+                    a.total_cmp(&b)
+                })
+                .unwrap();
+        }
+        index
+    }
+
+    fn uct(&self, node: &Node, log_visits: f64) -> f64 {
+        let visits = node.visits as f64;
+        node.wins / visits + self.config.exploration * (log_visits / visits).sqrt()
+    }
+
+    /// Adds a child of one of the untried moves of the node, and returns it.
+    /// Returns the node itself if the game has ended there.
+    fn expand(&mut self, index: usize) -> usize {
+        let tree = &mut self.nodes;
+        if tree[index].untried.is_empty() {
+            return index;
+        }
+        let i = self.rng.gen_range(0..tree[index].untried.len());
+        let m = tree[index].untried.swap_remove(i);
+        let parent = &tree[index];
+        let mover = parent.state.color();
+        let state = parent.state.play(m).expect("an untried move is legal");
+        tree.push(Node::new(state, Some(m), Some(index), mover));
+        let child = tree.len() - 1;
+        tree[index].children.push(child);
+        child
+    }
+
+    /// Plays the game to the end from the node, and returns the winner, or `None` for a draw.
+    fn rollout(&mut self, index: usize) -> Option<Color> {
+        let mut state = self.nodes[index].state.clone();
+        loop {
+            if let Some(result) = state.result() {
+                return result.winner;
+            }
+            let m = match self.config.rollout {
+                Rollout::Random => {
+                    let moves = state.legal_moves();
+                    moves[self.rng.gen_range(0..moves.len())]
+                },
+                Rollout::Greedy => Greedy.best_search_move(&state).expect("the game is not over"),
+            };
+            state = state.play(m).expect("a chosen move is legal");
+        }
+    }
+}
+
+impl Strategy for Mcts {
+    fn choose_move(&mut self, state: &GameState) -> Move {
+        self.search(state).map_or(Move::Pass, |result| result.best)
+    }
+}
+
+/// The result of a search by `Mcts`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MctsResult {
+    pub best: Move,
+    /// The number of times the best move was tried.
+    pub visits: u32,
+    /// The rate of the games won after the best move, counting a draw as half a win.
+    pub win_rate: f64,
+}
+
+struct Node {
+    state: SearchState,
+    /// The move which led to this node, or `None` for the root.
+    played: Option<Move>,
+    parent: Option<usize>,
+    children: Vec<usize>,
+    untried: Vec<Move>,
+    /// The color of the player who played `played`, for whom `wins` are counted.
+    mover: Color,
+    visits: u32,
+    wins: f64,
+}
+impl Node {
+    fn new(state: SearchState, played: Option<Move>, parent: Option<usize>, mover: Color) -> Self {
+        let untried = state.legal_moves();
+        Self { state, played, parent, children: vec![], untried, mover, visits: 0, wins: 0.0 }
+    }
+}
+
+fn backpropagate(tree: &mut [Node], mut index: usize, winner: Option<Color>) {
+    loop {
+        let node = &mut tree[index];
+        node.visits += 1;
+        node.wins += match winner {
+            Some(color) if color == node.mover => 1.0,
+            Some(_) => 0.0,
+            None => 0.5,
+        };
+        match node.parent {
+            Some(parent) => index = parent,
+            None => return,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod mcts_test {
+    use crate::{
+        controller::{com::mcts::{Mcts, Rollout}, game_state::GameState},
+        game::{
+            assets::{board::Board, piece::Piece, player::{Player, Players}},
+            rule::{color::Color, moves::Move, position::Position, rules::Rules}
+        }
+    };
+
+    fn com_to_move(board: &str) -> GameState {
+        GameState {
+            board: board.parse::<Board>().unwrap(),
+            ..GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 4, ..Rules::default() })
+        }
+    }
+
+    #[test]
+    fn when_game_can_be_won_at_once_then_the_winning_move_is_found() {
+        // only a 10 on a4 reverses the w10 and wins
+        let state = com_to_move("
+             w1  w1  w1  w1
+             b1  w1  w1  w1
+            w10  w1  w1  w1
+              .  w1  w1  b1
+        ");
+        let result = Mcts::new(200, 1).search(&state).unwrap();
+        assert_eq!(result.best, Move::put(Position{x: 0, y: 3}, Piece::new(Color::Black, 10)));
+        assert_eq!(result.win_rate, 1.0);
+    }
+
+    #[test]
+    fn when_seed_is_the_same_then_the_same_move_is_chosen() {
+        let state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 6, ..Rules::default() });
+        for rollout in [Rollout::Random, Rollout::Greedy] {
+            let com = Mcts::new(10, 9).with_rollout(rollout);
+            assert_eq!(com.search(&state), com.search(&state));
+        }
+    }
+
+    #[test]
+    fn when_iterations_are_more_then_the_best_move_is_tried_more() {
+        let state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size: 6, ..Rules::default() });
+        let few = Mcts::new(20, 3).search(&state).unwrap();
+        let many = Mcts::new(200, 3).search(&state).unwrap();
+        assert!(many.visits > few.visits);
+    }
+
+    #[test]
+    fn when_game_is_over_then_no_move_is_chosen() {
+        let state = com_to_move("
+            b1  .  .  .
+             .  .  .  .
+             .  .  .  .
+             .  .  . w1
+        ");
+        assert_eq!(Mcts::default().search(&state), None);
+    }
+}
@@ -0,0 +1,467 @@
+//! `OpeningBook` is the moves recommended in the first moves of a game, learned from self-play.
+//!
+//! A book is written as JSON, versioned by `BOOK_VERSION`, and looks like this:
+//!
+//! ```text
+//! {
+//!   "version": 1,
+//!   "rules": { "board_size": 8, "piece_set": {...}, "flip_rule": "StrictSum", "takebacks": true },
+//!   "plies": 4,
+//!   "positions": {
+//!     "1234567890": [{ "played": "B10c4", "games": 12, "wins": 7, "draws": 1 }, ...],
+//!     ...
+//!   }
+//! }
+//! ```
+//!
+//! * `plies` is the number of the first moves of a game for which the book is used.
+//! * The keys of `positions` are the hashes of `canonical` positions, and the moves are written
+//!   on the canonical board, so the 8 symmetric positions share their moves.
+//! * `games`, `wins` and `draws` count the games played after the move, seen from its player.
+//!   The `takebacks` of the `rules` are ignored when the book is used.
+//!
+//! The book built into the game is `assets/opening_book.json`, written by the `make_opening_book` binary.
+
+use std::{collections::BTreeMap, error::Error, fmt, sync::OnceLock};
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    controller::game_state::GameState,
+    game::{
+        assets::{player::{Player, Players}, strategy_config::StrategyConfig},
+        rule::{color::Color, moves::Move, rules::Rules, symmetry::Symmetry}
+    }
+};
+
+use super::zobrist;
+
+/// The version of the JSON schema written by `OpeningBook::to_json`.
+pub const BOOK_VERSION: u32 = 1;
+/// The moves whose lower bound of the win rate is within this margin of the best are chosen from the book.
+pub const MARGIN: f64 = 0.1;
+/// The number of games a move must have been played in to be chosen from the book.
+pub const MIN_GAMES: u32 = 20;
+/// The z-score of `BookMove::lower_bound`, with which the win rate is above the bound 95% of the time.
+pub const CONFIDENCE: f64 = 1.96;
+
+/// `OpeningBook` maps canonical positions to the moves played there and their results.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use number_reversi::controller::{com::opening_book::OpeningBook, game_state::GameState};
+/// use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+/// use number_reversi::game::rule::{color::Color, moves::Move, position::Position, rules::Rules};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+/// let mut book = OpeningBook::new(Rules::default(), 2);
+/// let m = Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 10));
+/// for _ in 0..20 {
+///     book.record(&state, m, Some(Color::Black));
+/// }
+///
+/// // the same move turned to any of the 4 puttable squares is in the book
+/// assert_eq!(book.moves(&state).len(), 4);
+/// let chosen = book.choose(&state, &mut ChaCha8Rng::seed_from_u64(1)).unwrap();
+/// assert!(book.moves(&state).iter().any(|book_move| book_move.played == chosen));
+/// assert_eq!(OpeningBook::from_json(&book.to_json()), Ok(book));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OpeningBook {
+    pub version: u32,
+    /// The rules of the games the book was learned from.
+    pub rules: Rules,
+    /// The number of the first moves of a game for which the book is used.
+    pub plies: usize,
+    pub positions: BTreeMap<u64, Vec<BookMove>>,
+}
+
+/// A move in an `OpeningBook` with the results of the games played after it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BookMove {
+    #[serde(with = "move_text")]
+    pub played: Move,
+    pub games: u32,
+    pub wins: u32,
+    pub draws: u32,
+}
+impl BookMove {
+    /// Returns the rate of the games won after the move, counting a draw as half a win.
+    pub fn win_rate(&self) -> f64 {
+        (self.wins as f64 + self.draws as f64 / 2.0) / self.games.max(1) as f64
+    }
+
+    /// Returns the Wilson lower bound of the win rate with the `CONFIDENCE`.
+    /// The fewer games the move was played in, the lower the bound is than the win rate,
+    /// so a move which won a few games by chance does not rank above a move played in many.
+    pub fn lower_bound(&self) -> f64 {
+        if self.games == 0 {
+            return 0.0;
+        }
+        let (n, p, z) = (self.games as f64, self.win_rate(), CONFIDENCE);
+        let spread = z * (p * (1.0 - p) / n + z * z / (4.0 * n * n)).sqrt();
+        (p + z * z / (2.0 * n) - spread) / (1.0 + z * z / n)
+    }
+}
+
+impl OpeningBook {
+    /// Creates an empty book for the first `plies` moves of games played with the `Rules`.
+    pub fn new(rules: Rules, plies: usize) -> Self {
+        Self { version: BOOK_VERSION, rules, plies, positions: BTreeMap::new() }
+    }
+
+    /// Returns the book built into the game.
+    pub fn builtin() -> &'static OpeningBook {
+        static BUILTIN: OnceLock<OpeningBook> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            OpeningBook::from_json(include_str!("../../../assets/opening_book.json"))
+                .expect("the builtin opening book is valid")
+        })
+    }
+
+    /// Returns whether the book is used for the `GameState`,
+    /// which is played with the rules of the book and within its first moves.
+    pub fn covers(&self, state: &GameState) -> bool {
+        state.move_number <= self.plies && state.rules == Rules { takebacks: state.rules.takebacks, ..self.rules.clone() }
+    }
+
+    /// Returns the moves of the book for the `GameState`, turned to its board.
+    /// The result is empty if the book does not cover the state or has no moves for it.
+    pub fn moves(&self, state: &GameState) -> Vec<BookMove> {
+        if !self.covers(state) {
+            return vec![];
+        }
+        let (hash, symmetries) = canonical_symmetries(state);
+        let legal_moves = state.legal_moves();
+        let mut moves = vec![];
+        // a state which is symmetric in itself has every move which is the same but for its symmetries
+        for book_move in self.positions.get(&hash).into_iter().flatten() {
+            for symmetry in &symmetries {
+                let played = symmetry.inverse().play(book_move.played, state.board.size());
+                if legal_moves.contains(&played) && moves.iter().all(|book_move: &BookMove| book_move.played != played) {
+                    moves.push(BookMove { played, ..*book_move });
+                }
+            }
+        }
+        moves
+    }
+
+    /// Chooses one of the moves of the book for the `GameState` at random, or returns `None` if there are none.
+    /// Only the moves played in at least `MIN_GAMES` games are ranked, by the `BookMove::lower_bound` of their win rate,
+    /// and the moves within `MARGIN` of the best of them are chosen, each as often as it has been played.
+    pub fn choose(&self, state: &GameState, rng: &mut impl Rng) -> Option<Move> {
+        let moves = self.moves(state).into_iter()
+            .filter(|book_move| book_move.games >= MIN_GAMES)
+            .collect::<Vec<_>>();
+        let best = moves.iter().map(BookMove::lower_bound).max_by(f64::total_cmp)?;
+        let candidates = moves.iter().filter(|book_move| book_move.lower_bound() >= best - MARGIN).collect::<Vec<_>>();
+        candidates.choose_weighted(rng, |book_move| book_move.games).ok().map(|book_move| book_move.played)
+    }
+
+    /// Counts a game in which the `Move` was played on the `GameState` and the `winner` won, or which was drawn.
+    pub fn record(&mut self, state: &GameState, m: Move, winner: Option<Color>) {
+        let (hash, symmetries) = canonical_symmetries(state);
+        // the moves which are the same but for the symmetries of the state are counted as one
+        let played = symmetries.iter()
+            .map(|symmetry| symmetry.play(m, state.board.size()))
+            .min_by_key(|played| match played {
+                Move::Put { position, piece } => (Some(*position), piece.value),
+                Move::Pass => (None, 0),
+            })
+            .expect("a state has at least the identity symmetry");
+        let moves = self.positions.entry(hash).or_default();
+        let index = match moves.iter().position(|book_move| book_move.played == played) {
+            Some(index) => index,
+            None => {
+                moves.push(BookMove { played, games: 0, wins: 0, draws: 0 });
+                moves.len() - 1
+            },
+        };
+        let book_move = &mut moves[index];
+        book_move.games += 1;
+        match winner {
+            Some(color) if color == state.turn.color => book_move.wins += 1,
+            Some(_) => {},
+            None => book_move.draws += 1,
+        }
+    }
+
+    /// Removes the moves played in fewer than `min_games` games, whose results say little.
+    pub fn prune(&mut self, min_games: u32) {
+        for moves in self.positions.values_mut() {
+            moves.retain(|book_move| book_move.games >= min_games);
+            moves.sort_by_key(|book_move| std::cmp::Reverse(book_move.games));
+        }
+        self.positions.retain(|_, moves| !moves.is_empty());
+    }
+
+    /// Returns the JSON of the book.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("an opening book is always serializable")
+    }
+
+    /// Reads a book from its JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns an `OpeningBookError` if the JSON is malformed or written in an unsupported version.
+    pub fn from_json(json: &str) -> Result<Self, OpeningBookError> {
+        let value = serde_json::from_str::<serde_json::Value>(json)
+            .map_err(|e| OpeningBookError::InvalidJson(e.to_string()))?;
+        let version = value.get("version").and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| OpeningBookError::InvalidJson("missing field `version`".to_string()))?;
+        if version != BOOK_VERSION as u64 {
+            return Err(OpeningBookError::UnsupportedVersion(version));
+        }
+        serde_json::from_value(value).map_err(|e| OpeningBookError::InvalidJson(e.to_string()))
+    }
+}
+
+/// Represents why an `OpeningBook` cannot be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OpeningBookError {
+    /// The JSON is malformed or does not follow the schema.
+    InvalidJson(String),
+    /// The JSON is written in a version of the schema that is not supported.
+    UnsupportedVersion(u64),
+}
+impl fmt::Display for OpeningBookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpeningBookError::InvalidJson(e) => write!(f, "invalid opening book: {}", e),
+            OpeningBookError::UnsupportedVersion(version) =>
+                write!(f, "opening book version {} is not supported (expected {})", version, BOOK_VERSION),
+        }
+    }
+}
+impl Error for OpeningBookError {}
+
+/// Returns the hash of the canonical form of the `GameState`, and the `Symmetry` which turns the state to it.
+/// The canonical form is the one of the 8 symmetric boards with the smallest Zobrist hash,
+/// so the symmetric states have the same canonical hash.
+pub fn canonical(state: &GameState) -> (u64, Symmetry) {
+    let (hash, symmetries) = canonical_symmetries(state);
+    (hash, symmetries[0])
+}
+
+/// Returns the hash of the canonical form of the `GameState`, and every `Symmetry` which turns the state to it.
+fn canonical_symmetries(state: &GameState) -> (u64, Vec<Symmetry>) {
+    let hashes = Symmetry::ALL.map(|symmetry| {
+        let turned = GameState { board: symmetry.board(&state.board), ..state.clone() };
+        zobrist::hash(&turned)
+    });
+    let hash = *hashes.iter().min().expect("there are 8 symmetries");
+    let symmetries = Symmetry::ALL.into_iter().zip(hashes).filter(|(_, h)| *h == hash).map(|(symmetry, _)| symmetry);
+    (hash, symmetries.collect())
+}
+
+/// Returns the move of the `OpeningBook` for the COM of the `StrategyConfig`, if the book has one.
+/// `StrategyConfig::Random` never uses the book, and the `rng` is drawn only when a move is chosen.
+pub fn book_move(book: &OpeningBook, state: &GameState, config: StrategyConfig, rng: &mut impl Rng) -> Option<Move> {
+    match config {
+        StrategyConfig::Random => None,
+        _ => book.choose(state, rng),
+    }
+}
+
+/// `BookGenerator` learns an `OpeningBook` from self-play.
+///
+/// Every game plays the first `plies` moves with the `opening` strategy, and is then played to the end
+/// by the `strategy` on both sides to find which openings win.
+/// Each move is played at random instead with the probability `exploration`,
+/// so that other openings are tried too and the games after the same opening differ.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::com::opening_book::BookGenerator;
+/// use number_reversi::game::{assets::strategy_config::StrategyConfig, rule::rules::Rules};
+///
+/// let generator = BookGenerator {
+///     games: 4, plies: 1, min_games: 1,
+///     opening: StrategyConfig::Greedy, exploration: 0.5, strategy: StrategyConfig::Random, seed: 0,
+/// };
+/// let book = generator.generate(Rules { board_size: 4, ..Rules::default() });
+/// assert_eq!(book.positions.len(), 1);
+/// assert!(book.positions.values().flatten().map(|book_move| book_move.games).sum::<u32>() == 4);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BookGenerator {
+    pub games: usize,
+    pub plies: usize,
+    /// The number of games a move must be played in to stay in the book.
+    pub min_games: u32,
+    /// The strategy which plays the first `plies` moves.
+    pub opening: StrategyConfig,
+    /// The probability that a move is played at random instead of by the `opening` or the `strategy`.
+    pub exploration: f64,
+    /// The strategy which plays the rest of the game.
+    pub strategy: StrategyConfig,
+    pub seed: u64,
+}
+impl BookGenerator {
+    pub fn generate(&self, rules: Rules) -> OpeningBook {
+        let mut book = OpeningBook::new(rules.clone(), self.plies);
+        let com = Player::Com(self.strategy);
+        for game in 0..self.games {
+            let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+            rng.set_stream(game as u64);
+            let mut state = GameState::with_rules(Players::new(com, com), rules.clone());
+            let mut opening = vec![];
+            while !state.is_over() {
+                let in_book = state.move_number <= self.plies;
+                let strategy = if in_book { self.opening } else { self.strategy };
+                let m = match state.legal_moves().choose(&mut rng) {
+                    Some(m) if rng.gen_bool(self.exploration) => *m,
+                    _ => strategy.build(ChaCha8Rng::seed_from_u64(rng.gen())).choose_move(&state),
+                };
+                if in_book {
+                    opening.push((state.clone(), m));
+                }
+                state = state.play(m).expect("a legal move can be played");
+            }
+            let winner = state.result().and_then(|result| result.winner);
+            for (state, m) in opening.into_iter().filter(|(_, m)| *m != Move::Pass) {
+                book.record(&state, m, winner);
+            }
+        }
+        book.prune(self.min_games);
+        book
+    }
+}
+
+/// Writes a `Move` as its text, such as `B7e3`.
+mod move_text {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+
+    use crate::game::rule::moves::Move;
+
+    pub fn serialize<S: Serializer>(m: &Move, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(m)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Move, D::Error> {
+        String::deserialize(deserializer)?.parse().map_err(de::Error::custom)
+    }
+}
+
+
+#[cfg(test)]
+mod opening_book_test {
+    use std::collections::HashSet;
+
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        controller::{
+            com::opening_book::{canonical, book_move, BookGenerator, OpeningBook, OpeningBookError, MIN_GAMES},
+            game_state::GameState
+        },
+        game::{
+            assets::{player::{Player, Players}, strategy_config::StrategyConfig},
+            rule::{color::Color, rules::Rules, symmetry::Symmetry}
+        }
+    };
+
+    #[test]
+    fn when_state_is_turned_or_flipped_then_its_canonical_hash_is_the_same() {
+        let mut state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        for m in ["B10e3", "W3f5", "B2d6"] {
+            state = state.play(m.parse().unwrap()).unwrap();
+        }
+        let (hash, _) = canonical(&state);
+        for symmetry in Symmetry::ALL {
+            let turned = GameState { board: symmetry.board(&state.board), ..state.clone() };
+            assert_eq!(canonical(&turned).0, hash, "{:?}", symmetry);
+        }
+    }
+
+    #[test]
+    fn when_book_is_generated_then_only_the_first_moves_are_covered() {
+        let rules = Rules { board_size: 4, ..Rules::default() };
+        let generator = BookGenerator {
+            games: 30, plies: 2, min_games: 2,
+            opening: StrategyConfig::Greedy, exploration: 0.5, strategy: StrategyConfig::Greedy, seed: 7,
+        };
+        let book = generator.generate(rules.clone());
+        assert!(book.positions.values().flatten().all(|book_move| book_move.games >= 2));
+
+        let state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), rules);
+        let next = state.play(book.moves(&state)[0].played).unwrap();
+        let after = next.play(next.legal_moves()[0]).unwrap();
+        assert!(book.covers(&next));
+        assert!(!book.covers(&after));
+        assert!(book.moves(&after).is_empty());
+        assert!(!book.covers(&GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM))));
+    }
+
+    #[test]
+    fn when_a_move_won_a_few_games_by_chance_then_the_main_line_is_still_chosen() {
+        let state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        let mut book = OpeningBook::new(Rules::default(), 2);
+        // (move, games, wins) of the main line, a move lucky in a few games, a rarely played move and a losing move
+        let records = [("B10e3", 1000, 540), ("B9e3", 50, 37), ("B1e3", MIN_GAMES - 1, MIN_GAMES - 1), ("B2e3", 1000, 300)];
+        for (m, games, wins) in records {
+            for game in 0..games {
+                let winner = if game < wins { Color::Black } else { Color::White };
+                book.record(&state, m.parse().unwrap(), Some(winner));
+            }
+        }
+
+        let moves = book.moves(&state);
+        let mut main_line = 0;
+        for seed in 0..100 {
+            let chosen = book.choose(&state, &mut ChaCha8Rng::seed_from_u64(seed)).unwrap();
+            let book_move = moves.iter().find(|book_move| book_move.played == chosen).unwrap();
+            assert!([540, 37].contains(&book_move.wins), "{:?}", book_move);
+            main_line += (book_move.wins == 540) as usize;
+        }
+        assert!(main_line > 80);
+    }
+
+    #[test]
+    fn when_book_has_the_position_then_com_plays_from_it() {
+        let state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        let mut book = OpeningBook::new(Rules::default(), 2);
+        // a move which lost its games is still played when it is the best the book has
+        for _ in 0..MIN_GAMES {
+            book.record(&state, "B10e3".parse().unwrap(), Some(Color::White));
+        }
+
+        let m = book_move(&book, &state, StrategyConfig::Greedy, &mut ChaCha8Rng::seed_from_u64(1)).unwrap();
+        assert!(book.moves(&state).iter().any(|book_move| book_move.played == m));
+        assert_eq!(book_move(&book, &state, StrategyConfig::Random, &mut ChaCha8Rng::seed_from_u64(1)), None);
+        let next = state.play(m).unwrap();
+        assert_eq!(book_move(&book, &next, StrategyConfig::Greedy, &mut ChaCha8Rng::seed_from_u64(1)), None);
+    }
+
+    #[test]
+    fn when_builtin_book_is_read_then_every_entry_was_kept_by_the_generator() {
+        let book = OpeningBook::builtin();
+        assert!(book.positions.values().flatten().all(|book_move| book_move.games >= MIN_GAMES));
+
+        // every position is reached from the start by the moves of the book within its plies
+        let mut reached = HashSet::new();
+        let mut states = vec![GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), book.rules.clone())];
+        while let Some(state) = states.pop() {
+            let moves = book.moves(&state);
+            if !moves.is_empty() {
+                reached.insert(canonical(&state).0);
+            }
+            states.extend(moves.iter().map(|book_move| state.play(book_move.played).unwrap()));
+        }
+        assert_eq!(reached, book.positions.keys().copied().collect());
+    }
+
+    #[test]
+    fn when_version_is_not_supported_then_book_is_rejected() {
+        let json = OpeningBook::new(Rules::default(), 4).to_json().replace("\"version\":1", "\"version\":9");
+        assert_eq!(OpeningBook::from_json(&json), Err(OpeningBookError::UnsupportedVersion(9)));
+    }
+}
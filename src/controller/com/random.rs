@@ -0,0 +1,28 @@
+use rand_chacha::ChaCha8Rng;
+
+use crate::{controller::game_state::GameState, game::rule::moves::Move};
+
+use super::strategy::Strategy;
+
+/// `Random` is the COM which puts a random piece on a random puttable square.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Random {
+    rng: ChaCha8Rng,
+}
+impl Random {
+    /// Creates a `Random` which draws its choices from the `rng`.
+    pub fn new(rng: ChaCha8Rng) -> Self {
+        Self { rng }
+    }
+}
+impl Strategy for Random {
+    fn choose_move(&mut self, state: &GameState) -> Move {
+        match state.legal_moves().as_slice() {
+            [] | [Move::Pass] => Move::Pass,
+            _ => Move::put(
+                state.legal_positions().get_puttable_position(&mut self.rng),
+                state.pieces.select_piece(state.turn, &mut self.rng)
+            ),
+        }
+    }
+}
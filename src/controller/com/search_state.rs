@@ -0,0 +1,237 @@
+use crate::{
+    controller::game_state::GameState,
+    game::{
+        assets::{board::Board, pieces::Pieces, square::Square},
+        rule::{color::Color, legal_moves::LegalMoves, moves::{Move, MoveError}, result::GameResult, score::Score, turn::Turn}
+    }
+};
+
+use super::{bit_state::BitState, greedy::score_difference, zobrist};
+
+/// `SearchState` is the state of a game the COMs search.
+///
+/// A game on the standard 8x8 board is searched on a `BitState`, which plays a move without
+/// any heap allocation, and a game on another board falls back to the `GameState`.
+/// Both play the same moves by the same rules, so a search gives the same result on either.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::search_state::SearchState, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+/// use number_reversi::game::rule::rules::Rules;
+///
+/// let state = GameState::new(Players::new(Player::Human, Player::Human));
+/// assert!(matches!(SearchState::from(&state), SearchState::Bits(_)));
+///
+/// let small = GameState::with_rules(Players::new(Player::Human, Player::Human), Rules { board_size: 6, ..Rules::default() });
+/// assert!(matches!(SearchState::from(&small), SearchState::Board(_)));
+/// assert_eq!(SearchState::from(&small).legal_moves(), small.legal_moves());
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchState {
+    Bits(BitState),
+    Board(Box<GameState>),
+}
+impl From<&GameState> for SearchState {
+    fn from(state: &GameState) -> Self {
+        match BitState::try_from(state) {
+            Ok(bits) => SearchState::Bits(bits),
+            Err(_) => SearchState::Board(Box::new(state.clone())),
+        }
+    }
+}
+impl SearchState {
+    /// Returns the color of the player to move.
+    pub fn color(&self) -> Color {
+        match self {
+            SearchState::Bits(state) => state.color,
+            SearchState::Board(state) => state.turn.color,
+        }
+    }
+
+    /// Returns every `Move` the player to move can make, in the order of `GameState::legal_moves`.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        match self {
+            SearchState::Bits(state) => state.legal_moves(),
+            SearchState::Board(state) => state.legal_moves(),
+        }
+    }
+
+    /// Returns the `SearchState` after the `Move` is played, as `GameState::play` does.
+    pub fn play(&self, m: Move) -> Result<Self, MoveError> {
+        match self {
+            SearchState::Bits(state) => state.play(m).map(SearchState::Bits),
+            SearchState::Board(state) => state.play(m).map(|state| SearchState::Board(Box::new(state))),
+        }
+    }
+
+    /// Returns the `GameResult` if the game has ended, otherwise `None`.
+    pub fn result(&self) -> Option<GameResult> {
+        match self {
+            SearchState::Bits(state) => state.result(),
+            SearchState::Board(state) => state.result(),
+        }
+    }
+
+    /// Returns whether the game has ended.
+    pub fn is_over(&self) -> bool {
+        match self {
+            SearchState::Bits(state) => state.is_over(),
+            SearchState::Board(state) => state.is_over(),
+        }
+    }
+
+    /// Returns the Zobrist hash of the state, which is the same as `zobrist::hash` of the `GameState`.
+    pub fn hash(&self) -> u64 {
+        match self {
+            SearchState::Bits(state) => zobrist::hash_bits(state),
+            SearchState::Board(state) => zobrist::hash(state),
+        }
+    }
+
+    /// Returns the hash of `next`, the state after the `Move` is played, from `hash`, the hash of this state.
+    pub fn update_hash(&self, hash: u64, m: Move, next: &SearchState) -> u64 {
+        match (self, next) {
+            (SearchState::Bits(state), SearchState::Bits(next)) => zobrist::update_bits(hash, state, next),
+            (SearchState::Board(state), SearchState::Board(next)) => zobrist::update(hash, state, m, next),
+            _ => next.hash(),
+        }
+    }
+
+    /// Returns the `Score` of the black and white pieces.
+    pub fn score(&self) -> Score {
+        match self {
+            SearchState::Bits(state) => state.score(),
+            SearchState::Board(state) => state.board.get_score(),
+        }
+    }
+
+    /// Returns the score of the `color` minus the score of its opponent.
+    pub fn score_difference(&self, color: Color) -> i64 {
+        match self {
+            SearchState::Bits(state) => {
+                let score = state.score();
+                let difference = score.black as i64 - score.white as i64;
+                match color {
+                    Color::Black => difference,
+                    Color::White => -difference,
+                }
+            },
+            SearchState::Board(state) => score_difference(&state.board, color),
+        }
+    }
+
+    /// Returns whether every square of the board has a piece.
+    pub fn is_full(&self) -> bool {
+        self.empties() == 0
+    }
+
+    /// Returns the number of empty squares of the board.
+    pub fn empties(&self) -> usize {
+        match self {
+            SearchState::Bits(state) => state.empties(),
+            SearchState::Board(state) => state.board.squares.iter().flatten().filter(|square| **square == Square::Empty).count(),
+        }
+    }
+
+    /// Returns whether no piece of any value is left for the color.
+    pub fn is_exhausted(&self, color: Color) -> bool {
+        match self {
+            SearchState::Bits(state) => state.is_exhausted(color),
+            SearchState::Board(state) => state.pieces.is_exhausted(Turn{color, player: state.players.get(color)}),
+        }
+    }
+
+    /// Returns the number of the squares where the color can put a piece.
+    pub fn mobility(&self, color: Color) -> usize {
+        match self {
+            SearchState::Bits(state) => state.mobility(color),
+            SearchState::Board(state) => LegalMoves::new(&state.board, Turn{color, player: state.players.get(color)}).moves.len(),
+        }
+    }
+
+    /// Returns the differences of the values of the pieces of the color on the corners
+    /// and on the other squares of the edges.
+    pub fn stability(&self, color: Color) -> (i64, i64) {
+        match self {
+            SearchState::Bits(state) => state.stability(color),
+            SearchState::Board(state) => stability(&state.board, color),
+        }
+    }
+
+    /// Returns the total value of the remaining pieces of the color.
+    pub fn inventory(&self, color: Color) -> i64 {
+        match self {
+            SearchState::Bits(state) => state.inventory(color),
+            SearchState::Board(state) => inventory(&state.pieces, color),
+        }
+    }
+}
+
+fn stability(board: &Board, color: Color) -> (i64, i64) {
+    let last = board.size() - 1;
+    let (mut corner, mut edge) = (0, 0);
+    for (x, column) in board.squares.iter().enumerate() {
+        for (y, square) in column.iter().enumerate() {
+            let value = match square {
+                Square::Put(piece) if piece.color == color => piece.value as i64,
+                Square::Put(piece) => -(piece.value as i64),
+                Square::Empty => continue,
+            };
+            match (x == 0 || x == last, y == 0 || y == last) {
+                (true, true) => corner += value,
+                (true, false) | (false, true) => edge += value,
+                (false, false) => {},
+            }
+        }
+    }
+    (corner, edge)
+}
+
+fn inventory(pieces: &Pieces, color: Color) -> i64 {
+    let pieces = match color {
+        Color::Black => &pieces.black,
+        Color::White => &pieces.white,
+    };
+    pieces.iter().map(|(v, n)| *v as i64 * *n as i64).sum()
+}
+
+
+#[cfg(test)]
+mod search_state_test {
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        controller::{com::{search_state::SearchState, zobrist}, game_state::GameState},
+        game::{assets::player::{Player, Players}, rule::color::Color}
+    };
+
+    #[test]
+    fn when_games_are_played_then_bits_and_board_agree() {
+        for seed in 0..3 {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+            let (mut bits, mut board) = (SearchState::from(&state), SearchState::Board(Box::new(state.clone())));
+            let (mut bits_hash, mut board_hash) = (bits.hash(), board.hash());
+            while let Some(m) = state.legal_moves().choose(&mut rng).copied() {
+                state = state.play(m).unwrap();
+                let (next_bits, next_board) = (bits.play(m).unwrap(), board.play(m).unwrap());
+                (bits_hash, board_hash) = (bits.update_hash(bits_hash, m, &next_bits), board.update_hash(board_hash, m, &next_board));
+                (bits, board) = (next_bits, next_board);
+                assert_eq!(bits_hash, zobrist::hash(&state));
+                assert_eq!(board_hash, bits_hash);
+                assert_eq!(bits.result(), board.result());
+                assert_eq!(bits.empties(), board.empties());
+                for color in [Color::Black, Color::White] {
+                    assert_eq!(bits.score_difference(color), board.score_difference(color));
+                    assert_eq!(bits.mobility(color), board.mobility(color));
+                    assert_eq!(bits.stability(color), board.stability(color));
+                    assert_eq!(bits.inventory(color), board.inventory(color));
+                    assert_eq!(bits.is_exhausted(color), board.is_exhausted(color));
+                }
+            }
+        }
+    }
+}
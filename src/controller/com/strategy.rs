@@ -0,0 +1,50 @@
+use rand::Rng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::{controller::game_state::GameState, game::{assets::strategy_config::StrategyConfig, rule::moves::Move}};
+
+use super::{alpha_beta::AlphaBeta, greedy::Greedy, mcts::Mcts, random::Random};
+
+/// `Strategy` decides the moves of a COM.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::{greedy::Greedy, strategy::Strategy}, game_state::GameState};
+/// use number_reversi::game::assets::player::{Player, Players};
+///
+/// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+/// let m = Greedy.choose_move(&state);
+/// assert!(state.legal_moves().contains(&m));
+/// ```
+pub trait Strategy {
+    /// Returns the `Move` to play for the player of the current turn.
+    /// Returns `Move::Pass` if the game is over, since there is no move to play.
+    fn choose_move(&mut self, state: &GameState) -> Move;
+}
+
+impl StrategyConfig {
+    /// Creates the `Strategy`, which draws its random choices from the `rng`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rand::SeedableRng;
+    /// use rand_chacha::ChaCha8Rng;
+    /// use number_reversi::controller::game_state::GameState;
+    /// use number_reversi::game::assets::{player::{Player, Players}, strategy_config::StrategyConfig};
+    ///
+    /// let state = GameState::new(Players::new(Player::RANDOM_COM, Player::Human));
+    /// let config = StrategyConfig::Mcts { iterations: 20 };
+    /// let m = config.build(ChaCha8Rng::seed_from_u64(1)).choose_move(&state);
+    /// assert_eq!(config.build(ChaCha8Rng::seed_from_u64(1)).choose_move(&state), m);
+    /// ```
+    pub fn build(&self, mut rng: ChaCha8Rng) -> Box<dyn Strategy> {
+        match *self {
+            StrategyConfig::Random => Box::new(Random::new(rng)),
+            StrategyConfig::Greedy => Box::new(Greedy),
+            StrategyConfig::AlphaBeta { depth, node_budget } => Box::new(AlphaBeta::from_config(depth, node_budget)),
+            StrategyConfig::Mcts { iterations } => Box::new(Mcts::new(iterations, rng.gen())),
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{controller::game_state::GameState, game::{assets::strategy_config::StrategyConfig, rule::moves::Move}};
+
+use super::{alpha_beta::{AlphaBeta, Deepening}, mcts::{Mcts, Tree}, opening_book::{book_move, OpeningBook}};
+
+/// The number of iterations of `Mcts` run in one step of `Thinking`.
+pub const MCTS_STEP: usize = 100;
+
+/// `ThinkRequest` is everything a COM needs to choose its move, which can be sent to a worker.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ThinkRequest {
+    pub state: GameState,
+    pub config: StrategyConfig,
+    /// The seed of the random choices of COM.
+    pub seed: u64,
+    /// The stream of the seed, which is the number of moves played before the state.
+    pub stream: u64,
+}
+impl ThinkRequest {
+    /// Returns the random number generator of the seed and the stream, the same as `GameEngine::rng`.
+    pub fn rng(&self) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(self.stream);
+        rng
+    }
+
+    /// Starts `Thinking` of the request.
+    pub fn thinking(&self) -> Thinking {
+        Thinking::new(&self.state, self.config, self.rng())
+    }
+}
+
+/// `Thinking` is the search of a COM split into steps, so that its progress can be reported
+/// and it can be cancelled between the steps.
+/// `AlphaBeta` is deepened one depth a step, and `Mcts` runs `MCTS_STEP` iterations a step,
+/// while the other strategies and the moves from the opening book are chosen at once.
+/// The move finally chosen is the same as the one chosen by `Strategy::choose_move`.
+///
+/// # Examples
+///
+/// ```
+/// use rand::SeedableRng;
+/// use rand_chacha::ChaCha8Rng;
+/// use number_reversi::controller::{com::thinking::{Step, Thinking}, game_state::GameState};
+/// use number_reversi::game::assets::{player::{Player, Players}, strategy_config::StrategyConfig};
+/// use number_reversi::game::rule::rules::Rules;
+///
+/// let state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::Human), Rules { board_size: 6, ..Rules::default() });
+/// let config = StrategyConfig::AlphaBeta { depth: 2, node_budget: None };
+/// let mut thinking = Thinking::new(&state, config, ChaCha8Rng::seed_from_u64(1));
+/// let Step::Thinking(progress) = thinking.step() else { panic!() };
+/// assert_eq!((progress.done, progress.total), (1, 2));
+/// let Step::Done(m) = thinking.step() else { panic!() };
+/// assert_eq!(config.build(ChaCha8Rng::seed_from_u64(1)).choose_move(&state), m);
+/// ```
+pub struct Thinking {
+    search: Search,
+    total: usize,
+}
+
+enum Search {
+    Done(Move),
+    AlphaBeta(Deepening),
+    Mcts(Tree),
+}
+
+/// The progress of `Thinking`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct Progress {
+    /// The number of depths or iterations searched.
+    pub done: usize,
+    /// The number of depths or iterations to search.
+    pub total: usize,
+    /// The best move found so far.
+    pub best: Option<Move>,
+}
+
+/// The result of a step of `Thinking`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Thinking(Progress),
+    /// The chosen move, which is `Move::Pass` if the game is over.
+    Done(Move),
+}
+
+impl Thinking {
+    /// Starts thinking about the move of the `GameState` with the strategy of the `config`,
+    /// which draws its random choices from the `rng`.
+    pub fn new(state: &GameState, config: StrategyConfig, mut rng: ChaCha8Rng) -> Self {
+        if let Some(m) = book_move(OpeningBook::builtin(), state, config, &mut rng) {
+            return Self { search: Search::Done(m), total: 1 };
+        }
+        let (search, total) = match config {
+            StrategyConfig::AlphaBeta { depth, node_budget } => {
+                let config = AlphaBeta::from_config(depth, node_budget);
+                (Search::AlphaBeta(Deepening::new(config, state.clone())), config.depth)
+            },
+            StrategyConfig::Mcts { iterations } => {
+                let config = Mcts::new(iterations, rng.gen());
+                match Tree::new(config, state.clone()) {
+                    Some(tree) => (Search::Mcts(tree), config.iterations),
+                    None => (Search::Done(Move::Pass), 1),
+                }
+            },
+            StrategyConfig::Random | StrategyConfig::Greedy => (Search::Done(config.build(rng).choose_move(state)), 1),
+        };
+        Self { search, total }
+    }
+
+    /// Searches one more step, and returns the progress, or the chosen move when the search is over.
+    pub fn step(&mut self) -> Step {
+        let (finished, done, best) = match &mut self.search {
+            Search::Done(m) => return Step::Done(*m),
+            Search::AlphaBeta(deepening) => {
+                let finished = !deepening.deepen();
+                (finished, deepening.depth(), deepening.result().map(|result| result.best))
+            },
+            Search::Mcts(tree) => {
+                tree.grow(MCTS_STEP);
+                (tree.iterations() >= self.total, tree.iterations(), tree.result().map(|result| result.best))
+            },
+        };
+        if finished {
+            let m = best.unwrap_or(Move::Pass);
+            self.search = Search::Done(m);
+            return Step::Done(m);
+        }
+        Step::Thinking(Progress { done, total: self.total, best })
+    }
+}
+
+
+#[cfg(test)]
+mod thinking_test {
+    use crate::{
+        controller::{
+            com::{alpha_beta::AlphaBeta, endgame::empties, opening_book::OpeningBook, thinking::{Step, ThinkRequest}},
+            game_engine::GameEngine
+        },
+        game::{assets::{player::{Player, Players}, strategy_config::StrategyConfig}, rule::rules::Rules}
+    };
+
+    #[test]
+    fn when_thinking_is_stepped_to_the_end_then_the_move_of_com_is_chosen() {
+        let configs = [
+            StrategyConfig::Random,
+            StrategyConfig::Greedy,
+            StrategyConfig::AlphaBeta { depth: 3, node_budget: Some(2_000) },
+            StrategyConfig::Mcts { iterations: 250 },
+        ];
+        for config in configs {
+            let players = Players::new(Player::Com(config), Player::Human);
+            let mut engine = GameEngine::with_rules(players, Rules { board_size: 6, ..Rules::default() }).with_seed(5);
+            engine.play(engine.random_move().unwrap()).unwrap();
+            engine.play(engine.random_move().unwrap()).unwrap();
+
+            let request = engine.think_request().unwrap();
+            let mut thinking = request.thinking();
+            let m = loop {
+                match thinking.step() {
+                    Step::Thinking(progress) => assert!(progress.done < progress.total),
+                    Step::Done(m) => break m,
+                }
+            };
+            assert_eq!(Some(m), engine.com_move(), "{:?}", config);
+            assert_eq!(thinking.step(), Step::Done(m));
+        }
+    }
+
+    #[test]
+    fn when_endgame_solver_gives_up_then_the_next_moves_are_still_the_moves_of_com() {
+        let config = StrategyConfig::AlphaBeta { depth: 2, node_budget: Some(2_000) };
+        let solver = AlphaBeta::from_config(2, Some(2_000)).endgame.unwrap();
+        let mut engine = GameEngine::new(Players::new(Player::Com(config), Player::Com(config))).with_seed(4);
+        while !engine.is_over() && empties(engine.state()) > solver.empties {
+            engine.play(engine.random_move().unwrap()).unwrap();
+        }
+
+        let (mut gave_up, mut solved_after_giving_up) = (false, false);
+        while let Some(request) = engine.think_request() {
+            let solved = solver.solve(engine.state()).is_some();
+            solved_after_giving_up |= gave_up && solved;
+            gave_up |= !solved;
+            let mut thinking = request.thinking();
+            let m = loop {
+                if let Step::Done(m) = thinking.step() {
+                    break m;
+                }
+            };
+            assert_eq!(Some(m), engine.com_move());
+            engine.play(m).unwrap();
+        }
+        assert!(solved_after_giving_up);
+    }
+
+    #[test]
+    fn when_opening_book_has_the_state_then_its_move_is_chosen_at_once() {
+        let engine = GameEngine::new(Players::new(Player::Com(StrategyConfig::Greedy), Player::Human)).with_seed(2);
+        let mut thinking = engine.think_request().unwrap().thinking();
+        let Step::Done(m) = thinking.step() else { panic!("the book is not used") };
+        assert_eq!(Some(m), engine.com_move());
+        assert!(OpeningBook::builtin().moves(engine.state()).iter().any(|book_move| book_move.played == m));
+    }
+
+    #[test]
+    fn when_it_is_not_the_turn_of_com_then_there_is_no_request() {
+        let engine = GameEngine::new(Players::new(Player::Human, Player::RANDOM_COM));
+        assert_eq!(engine.think_request(), None);
+
+        let mut engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM)).with_seed(3);
+        engine.play(engine.random_move().unwrap()).unwrap();
+        let request: ThinkRequest = engine.think_request().unwrap();
+        assert_eq!(request.stream, 1);
+        assert_eq!(request.rng(), engine.rng());
+    }
+}
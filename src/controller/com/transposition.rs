@@ -0,0 +1,105 @@
+/// The default number of entries of a `TranspositionTable`.
+pub const DEFAULT_CAPACITY: usize = 1 << 15;
+
+/// `TranspositionTable` remembers a value for each position by its Zobrist hash, up to a fixed
+/// number of entries, so that a search can reuse what it found when it reaches a position again.
+///
+/// Every hash has one slot chosen by its lowest bits, and a value inserted into a slot replaces
+/// the value of any other hash there, so the table never grows beyond its capacity.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::com::transposition::TranspositionTable;
+///
+/// let mut table = TranspositionTable::new(4);
+/// table.insert(1, "a");
+/// assert_eq!(table.get(1), Some(&"a"));
+/// assert_eq!(table.get(5), None);
+/// // 5 has the same slot as 1
+/// table.insert(5, "b");
+/// assert_eq!(table.get(1), None);
+/// assert_eq!(table.len(), 1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TranspositionTable<V> {
+    slots: Vec<Option<(u64, V)>>,
+    len: usize,
+}
+impl<V> Default for TranspositionTable<V> {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+impl<V> TranspositionTable<V> {
+    /// Creates an empty table with at most `capacity` entries.
+    /// The capacity is rounded down to a power of two, and is at least 1.
+    pub fn new(capacity: usize) -> Self {
+        let capacity = 1 << capacity.max(1).ilog2();
+        Self { slots: (0..capacity).map(|_| None).collect(), len: 0 }
+    }
+
+    /// Returns the number of entries the table can hold.
+    pub fn capacity(&self) -> usize {
+        self.slots.len()
+    }
+
+    /// Returns the number of entries in the table.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the value of the hash, or `None` if it is not in the table.
+    pub fn get(&self, hash: u64) -> Option<&V> {
+        match &self.slots[self.slot(hash)] {
+            Some((h, value)) if *h == hash => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Inserts the value of the hash, replacing the entry in its slot, and returns the replaced value.
+    pub fn insert(&mut self, hash: u64, value: V) -> Option<V> {
+        let slot = self.slot(hash);
+        let replaced = self.slots[slot].replace((hash, value));
+        if replaced.is_none() {
+            self.len += 1;
+        }
+        replaced.map(|(_, value)| value)
+    }
+
+    /// Removes every entry.
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+        self.len = 0;
+    }
+
+    fn slot(&self, hash: u64) -> usize {
+        hash as usize & (self.slots.len() - 1)
+    }
+}
+
+
+#[cfg(test)]
+mod transposition_test {
+    use crate::controller::com::transposition::TranspositionTable;
+
+    #[test]
+    fn when_table_is_full_then_it_does_not_grow() {
+        let mut table = TranspositionTable::new(10);
+        assert_eq!(table.capacity(), 8);
+        for hash in 0..100 {
+            table.insert(hash, hash);
+        }
+        assert_eq!(table.len(), 8);
+        assert_eq!(table.get(99), Some(&99));
+        assert_eq!(table.get(3), None);
+
+        table.clear();
+        assert!(table.is_empty());
+        assert_eq!(table.get(99), None);
+    }
+}
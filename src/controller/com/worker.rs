@@ -0,0 +1,93 @@
+use gloo_timers::callback::Timeout;
+use gloo_worker::{HandlerId, Worker, WorkerScope};
+use serde::{Deserialize, Serialize};
+
+use crate::game::rule::moves::Move;
+
+use super::thinking::{Progress, Step, ThinkRequest, Thinking};
+
+/// The path of the script of `ComWorker` built by trunk, relative to the page.
+pub const WORKER_PATH: &str = "com_worker.js";
+
+/// The messages sent to `ComWorker`.
+/// Every search has an id, so that the outputs of a search which is no longer wanted can be told apart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ComInput {
+    /// Starts thinking about the request, cancelling the search in progress.
+    Think { id: u64, request: Box<ThinkRequest> },
+    /// Cancels the search of the id if it is still in progress.
+    Cancel { id: u64 },
+}
+
+/// The messages sent back from `ComWorker`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ComOutput {
+    /// Sent after every step of the search.
+    Progress { id: u64, progress: Progress },
+    /// Sent when the search is over, with the chosen move.
+    Done { id: u64, played: Move },
+}
+
+pub enum ComMessage {
+    Step,
+}
+
+/// `ComWorker` is the web worker which thinks about the moves of COM off the UI thread.
+///
+/// The search is run one step of `Thinking` at a time, and the next step is scheduled with a timeout,
+/// so that a `ComInput::Cancel` received between the steps stops it.
+pub struct ComWorker {
+    thinking: Option<(u64, HandlerId, Thinking)>,
+    next_step: Option<Timeout>,
+}
+
+impl Worker for ComWorker {
+    type Message = ComMessage;
+    type Input = ComInput;
+    type Output = ComOutput;
+
+    fn create(_scope: &WorkerScope<Self>) -> Self {
+        Self { thinking: None, next_step: None }
+    }
+
+    fn update(&mut self, scope: &WorkerScope<Self>, msg: Self::Message) {
+        let ComMessage::Step = msg;
+        let Some((id, handler, thinking)) = &mut self.thinking else {
+            return;
+        };
+        let (id, handler) = (*id, *handler);
+        match thinking.step() {
+            Step::Thinking(progress) => {
+                scope.respond(handler, ComOutput::Progress { id, progress });
+                self.schedule(scope);
+            },
+            Step::Done(played) => {
+                scope.respond(handler, ComOutput::Done { id, played });
+                self.thinking = None;
+            },
+        }
+    }
+
+    fn received(&mut self, scope: &WorkerScope<Self>, msg: Self::Input, handler: HandlerId) {
+        match msg {
+            ComInput::Think { id, request } => {
+                self.thinking = Some((id, handler, request.thinking()));
+                self.schedule(scope);
+            },
+            ComInput::Cancel { id } => {
+                if matches!(self.thinking, Some((current, _, _)) if current == id) {
+                    self.thinking = None;
+                    self.next_step = None;
+                }
+            },
+        }
+    }
+}
+
+impl ComWorker {
+    /// Runs the next step after the messages which have arrived are received.
+    fn schedule(&mut self, scope: &WorkerScope<Self>) {
+        let step = scope.callback(|()| ComMessage::Step);
+        self.next_step = Some(Timeout::new(0, move || step(())));
+    }
+}
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use crate::{
+    controller::game_state::GameState,
+    game::{
+        assets::{bit_board::{bits, BitBoard}, piece::Piece, pieces::Pieces, square::Square},
+        rule::{color::Color, moves::Move, position::Position}
+    }
+};
+
+use super::bit_state::BitState;
+
+/// Returns the Zobrist hash of the `GameState`.
+///
+/// The hash is the XOR of a random key for every piece on the board with its position, color and value,
+/// for the color to move, for the number of the remaining pieces of every color and value,
+/// and for every used piece, which decides the penalty of a pass.
+/// Two states which differ in any of them have different hashes but with a negligible chance,
+/// while the players, the rules and the move number are not hashed.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{com::zobrist, game_state::GameState};
+/// use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+/// use number_reversi::game::rule::{color::Color, moves::Move, position::Position};
+///
+/// let state = GameState::new(Players::new(Player::Human, Player::Human));
+/// let m = Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 3));
+/// let next = state.play(m).unwrap();
+/// assert_ne!(zobrist::hash(&next), zobrist::hash(&state));
+/// assert_eq!(zobrist::update(zobrist::hash(&state), &state, m, &next), zobrist::hash(&next));
+/// ```
+pub fn hash(state: &GameState) -> u64 {
+    let mut hash = turn_key(state.turn.color) ^ inventory_key(&state.pieces);
+    for piece in &state.used.piece_set {
+        hash ^= used_key(piece);
+    }
+    for (x, column) in state.board.squares.iter().enumerate() {
+        for (y, square) in column.iter().enumerate() {
+            hash ^= square_key(x, y, square);
+        }
+    }
+    hash
+}
+
+/// Returns the hash of `after`, the `GameState` after the `Move` is played on `before`,
+/// from `hash`, the hash of `before`.
+///
+/// Only the squares on the lines through the put piece are compared, since no other square changes,
+/// so this is much faster than `hash(after)` while giving the same hash.
+pub fn update(hash: u64, before: &GameState, m: Move, after: &GameState) -> u64 {
+    let mut hash = hash;
+    if let Move::Put { position, .. } = m {
+        let size = before.board.size() as i64;
+        let (x, y) = (position.x, position.y);
+        hash ^= square_key(x, y, &before.board.squares[x][y]) ^ square_key(x, y, &after.board.squares[x][y]);
+        for (dx, dy) in DIRECTIONS {
+            let (mut x, mut y) = (position.x as i64 + dx, position.y as i64 + dy);
+            while (0..size).contains(&x) && (0..size).contains(&y) {
+                let (before, after) = (&before.board.squares[x as usize][y as usize], &after.board.squares[x as usize][y as usize]);
+                if before != after {
+                    hash ^= square_key(x as usize, y as usize, before) ^ square_key(x as usize, y as usize, after);
+                }
+                (x, y) = (x + dx, y + dy);
+            }
+        }
+    }
+    if before.turn.color != after.turn.color {
+        hash ^= turn_key(before.turn.color) ^ turn_key(after.turn.color);
+    }
+    for piece in before.used.piece_set.symmetric_difference(&after.used.piece_set) {
+        hash ^= used_key(piece);
+    }
+    hash ^ inventory_difference(Color::Black, &before.pieces.black, &after.pieces.black)
+        ^ inventory_difference(Color::White, &before.pieces.white, &after.pieces.white)
+}
+
+/// Returns the Zobrist hash of the `BitState`, which is the same as `hash` of the `GameState` it was made from.
+pub fn hash_bits(state: &BitState) -> u64 {
+    let mut hash = turn_key(state.color);
+    for color in [Color::Black, Color::White] {
+        for (v, n) in state.pieces(color).iter().enumerate() {
+            hash ^= count_key(color, v as u8, *n);
+        }
+        hash ^= used_bits_key(color, state.used(color));
+    }
+    for i in bits(state.board.occupied()) {
+        hash ^= bit_key(&state.board, i);
+    }
+    hash
+}
+
+/// Returns the hash of `after`, the `BitState` after a move is played on `before`, from `hash`, the hash of `before`.
+///
+/// The changed squares, counts and used pieces are found by comparing the masks and the arrays,
+/// so this gives the same hash as `hash_bits(after)` without looking at anything else.
+pub fn update_bits(hash: u64, before: &BitState, after: &BitState) -> u64 {
+    let mut hash = hash;
+    let changed = (before.board.black ^ after.board.black) | (before.board.white ^ after.board.white);
+    for i in bits(changed) {
+        hash ^= bit_key(&before.board, i) ^ bit_key(&after.board, i);
+    }
+    if before.color != after.color {
+        hash ^= turn_key(before.color) ^ turn_key(after.color);
+    }
+    for color in [Color::Black, Color::White] {
+        hash ^= used_bits_key(color, before.used(color) ^ after.used(color));
+        let counts = before.pieces(color).iter().zip(after.pieces(color)).enumerate();
+        for (v, (n, m)) in counts.filter(|(_, (n, m))| n != m) {
+            hash ^= count_key(color, v as u8, *n) ^ count_key(color, v as u8, *m);
+        }
+    }
+    hash
+}
+
+const DIRECTIONS: [(i64, i64); 8] = [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+const SQUARE: u64 = 1;
+const TURN: u64 = 2;
+const INVENTORY: u64 = 3;
+const USED: u64 = 4;
+
+/// Returns the random key of a feature of a state, which is fixed so that hashes are the same on every run.
+fn key(kind: u64, a: u64, b: u64, color: Color, value: u64) -> u64 {
+    let color = match color {
+        Color::Black => 0,
+        Color::White => 1,
+    };
+    splitmix64(kind << 56 | a << 40 | b << 24 | color << 16 | value)
+}
+
+/// SplitMix64, which scatters every bit of the input over the output.
+fn splitmix64(x: u64) -> u64 {
+    let mut z = x.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+fn square_key(x: usize, y: usize, square: &Square) -> u64 {
+    match square {
+        Square::Put(piece) => key(SQUARE, x as u64, y as u64, piece.color, piece.value as u64),
+        Square::Empty => 0,
+    }
+}
+
+/// Returns the key of the piece at the bit index of the `BitBoard`, which is 0 when the square is empty.
+fn bit_key(board: &BitBoard, i: usize) -> u64 {
+    match board.get(Position{x: i % 8, y: i / 8}) {
+        Some(piece) => key(SQUARE, (i % 8) as u64, (i / 8) as u64, piece.color, piece.value as u64),
+        None => 0,
+    }
+}
+
+fn turn_key(color: Color) -> u64 {
+    key(TURN, 0, 0, color, 0)
+}
+
+/// Returns the key of a number of the remaining pieces of a value, which is 0 when there are none.
+fn count_key(color: Color, value: u8, count: u8) -> u64 {
+    match count {
+        0 => 0,
+        count => key(INVENTORY, value as u64, count as u64, color, 0),
+    }
+}
+
+fn used_key(piece: &Piece) -> u64 {
+    key(USED, 0, 0, piece.color, piece.value as u64)
+}
+
+/// Returns the XOR of the keys of the used pieces of the color whose values are set in the mask.
+fn used_bits_key(color: Color, mask: u16) -> u64 {
+    (0..16).filter(|v| mask & 1 << v != 0).fold(0, |hash, v| hash ^ used_key(&Piece::new(color, v as u8)))
+}
+
+fn inventory_key(pieces: &Pieces) -> u64 {
+    let keys = |color, pieces: &HashMap<u8, u8>| pieces.iter().fold(0, |hash, (v, n)| hash ^ count_key(color, *v, *n));
+    keys(Color::Black, &pieces.black) ^ keys(Color::White, &pieces.white)
+}
+
+/// Returns the XOR of the keys of the counts which differ between the inventories of a color.
+fn inventory_difference(color: Color, before: &HashMap<u8, u8>, after: &HashMap<u8, u8>) -> u64 {
+    let mut hash = 0;
+    for (v, n) in before {
+        let m = after.get(v).copied().unwrap_or(0);
+        if *n != m {
+            hash ^= count_key(color, *v, *n) ^ count_key(color, *v, m);
+        }
+    }
+    for (v, m) in after {
+        if !before.contains_key(v) {
+            hash ^= count_key(color, *v, *m);
+        }
+    }
+    hash
+}
+
+
+#[cfg(test)]
+mod zobrist_test {
+    use rand::{seq::SliceRandom, SeedableRng};
+    use rand_chacha::ChaCha8Rng;
+
+    use crate::{
+        controller::{com::zobrist::{hash, update}, game_state::GameState},
+        game::{
+            assets::{piece::Piece, player::{Player, Players}},
+            rule::{color::Color, moves::Move, position::Position, rules::Rules}
+        }
+    };
+
+    #[test]
+    fn when_hash_is_updated_through_a_game_then_it_equals_the_hash_of_every_state() {
+        for (board_size, seed) in [(4, 1), (6, 2), (8, 3)] {
+            let mut rng = ChaCha8Rng::seed_from_u64(seed);
+            let mut state = GameState::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), Rules { board_size, ..Rules::default() });
+            let mut current = hash(&state);
+            while let Some(m) = state.legal_moves().choose(&mut rng).copied() {
+                let next = state.play(m).unwrap();
+                current = update(current, &state, m, &next);
+                assert_eq!(current, hash(&next), "{}\n{}", m, next.board);
+                state = next;
+            }
+        }
+    }
+
+    #[test]
+    fn when_value_or_inventory_or_turn_or_used_pieces_differ_then_hash_differs() {
+        let state = GameState::new(Players::new(Player::Human, Player::Human));
+        let put = |value| state.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, value))).unwrap();
+        assert_ne!(hash(&put(3)), hash(&put(4)));
+
+        let mut fewer = state.clone();
+        *fewer.pieces.white.get_mut(&1).unwrap() -= 1;
+        assert_ne!(hash(&fewer), hash(&state));
+
+        let mut white = state.clone();
+        white.turn.color = Color::White;
+        assert_ne!(hash(&white), hash(&state));
+
+        let used = GameState { used: state.used.add_piece(Piece::new(Color::Black, 7)), ..state.clone() };
+        assert_ne!(hash(&used), hash(&state));
+    }
+}
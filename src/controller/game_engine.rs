@@ -0,0 +1,470 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha8Rng;
+
+use crate::controller::com::{
+    endgame::{EndgameSolver, Solution}, opening_book::{book_move, OpeningBook}, random::Random, strategy::Strategy, thinking::ThinkRequest
+};
+
+use crate::game::{
+    assets::{board::Board, piece::{Piece, UsedPiece}, pieces::Pieces, player::{Player, Players}},
+    rule::{
+        legal_moves::LegalMoves, moves::{Move, MoveError}, position::Position, result::GameResult, rules::Rules,
+        turn::Turn
+    }
+};
+
+use super::{game_state::GameState, history::{History, HistoryError, MoveRecord}};
+
+/// Represents whether a game is still being played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GameStatus {
+    InProgress,
+    Finished(GameResult),
+}
+
+/// `GameEngine` owns the whole state of a game and drives it move by move.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::game_engine::GameEngine;
+/// use number_reversi::game::assets::player::{Player, Players};
+///
+/// let mut engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+/// while !engine.is_over() {
+///     let m = engine.legal_moves()[0];
+///     engine.play(m).unwrap();
+/// }
+/// assert!(engine.move_number() > 1);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameEngine {
+    history: History,
+    status: GameStatus,
+    /// The seed of the random choices of COM.
+    seed: u64,
+}
+impl GameEngine {
+    /// Creates a new `GameEngine` at the start of a game.
+    pub fn new(players: Players) -> Self {
+        Self::from_state(GameState::new(players))
+    }
+
+    /// Creates a new `GameEngine` at the start of a game played with the `Rules`.
+    pub fn with_rules(players: Players, rules: Rules) -> Self {
+        Self::from_state(GameState::with_rules(players, rules))
+    }
+
+    /// Creates a new `GameEngine` which continues the game from the `GameState`.
+    /// The seed of the game is chosen at random.
+    pub fn from_state(state: GameState) -> Self {
+        let mut engine = Self { history: History::new(state), status: GameStatus::InProgress, seed: rand::random() };
+        engine.update_status();
+        engine
+    }
+
+    /// Replaces the seed of the game, so that the random choices of COM can be reproduced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_engine::GameEngine;
+    /// use number_reversi::game::assets::player::{Player, Players};
+    ///
+    /// let play = |seed| {
+    ///     let mut engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM)).with_seed(seed);
+    ///     while let Some(m) = engine.random_move() {
+    ///         engine.play(m).unwrap();
+    ///     }
+    ///     engine
+    /// };
+    /// assert_eq!(play(42).state(), play(42).state());
+    /// ```
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Returns the seed of the game.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// Returns the random number generator for the current move.
+    ///
+    /// It is derived from the seed and the number of moves played, so it draws the same numbers
+    /// whenever the game reaches the same move, even after an undo or loading a record.
+    pub fn rng(&self) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(self.history().len() as u64);
+        rng
+    }
+
+    /// Returns a random legal `Move` drawn from `GameEngine::rng`, or `None` if the game is over.
+    pub fn random_move(&self) -> Option<Move> {
+        if self.is_over() {
+            return None;
+        }
+        Some(Random::new(self.rng()).choose_move(self.state()))
+    }
+
+    /// Returns the `Move` chosen by the `Strategy` of the COM of the current turn,
+    /// or `None` if the game is over or it is the turn of a human.
+    /// The strategy draws its random choices from `GameEngine::rng`.
+    /// In the first moves, a move of the builtin `OpeningBook` is chosen instead if it has one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_engine::GameEngine;
+    /// use number_reversi::game::assets::{player::{Player, Players}, strategy_config::StrategyConfig};
+    ///
+    /// let mut engine = GameEngine::new(Players::new(Player::Com(StrategyConfig::Greedy), Player::Human));
+    /// let m = engine.com_move().unwrap();
+    /// engine.play(m).unwrap();
+    /// assert_eq!(engine.com_move(), None);
+    /// ```
+    pub fn com_move(&self) -> Option<Move> {
+        match self.turn().player {
+            Player::Com(config) if !self.is_over() => {
+                let mut rng = self.rng();
+                let m = book_move(OpeningBook::builtin(), self.state(), config, &mut rng);
+                Some(m.unwrap_or_else(|| config.build(rng).choose_move(self.state())))
+            },
+            _ => None,
+        }
+    }
+
+    /// Returns the `ThinkRequest` of the COM of the current turn,
+    /// or `None` if the game is over or it is the turn of a human.
+    /// Thinking about it to the end chooses the same move as `GameEngine::com_move`.
+    pub fn think_request(&self) -> Option<ThinkRequest> {
+        match self.turn().player {
+            Player::Com(config) if !self.is_over() => Some(ThinkRequest {
+                state: self.state().clone(),
+                config,
+                seed: self.seed,
+                stream: self.history().len() as u64,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Solves the rest of the game with the `EndgameSolver`, for analysing the current state.
+    /// Returns `None` if the node budget of the solver runs out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::{com::endgame::EndgameSolver, game_engine::GameEngine};
+    /// use number_reversi::game::assets::player::{Player, Players};
+    ///
+    /// let engine = GameEngine::new(Players::new(Player::Human, Player::Human));
+    /// let solver = EndgameSolver { node_budget: Some(1_000), ..EndgameSolver::default() };
+    /// assert_eq!(engine.solve_endgame(&solver), None);
+    /// ```
+    pub fn solve_endgame(&self, solver: &EndgameSolver) -> Option<Solution> {
+        solver.solve(self.state())
+    }
+
+    pub fn state(&self) -> &GameState {
+        self.history.current()
+    }
+
+    pub fn board(&self) -> &Board {
+        &self.state().board
+    }
+
+    pub fn pieces(&self) -> &Pieces {
+        &self.state().pieces
+    }
+
+    pub fn used(&self) -> &UsedPiece {
+        &self.state().used
+    }
+
+    pub fn turn(&self) -> Turn {
+        self.state().turn
+    }
+
+    pub fn rules(&self) -> &Rules {
+        &self.state().rules
+    }
+
+    pub fn move_number(&self) -> usize {
+        self.state().move_number
+    }
+
+    pub fn status(&self) -> GameStatus {
+        self.status
+    }
+
+    /// Returns every `Move` the player of the current turn can make.
+    pub fn legal_moves(&self) -> Vec<Move> {
+        self.state().legal_moves()
+    }
+
+    /// Returns the puttable positions of the current turn with their `ReversibleCandidates`.
+    pub fn legal_positions(&self) -> LegalMoves {
+        self.state().legal_positions()
+    }
+
+    /// Returns the positions of the pieces that would be reversed if the `Piece` were put on the `Position`.
+    pub fn preview(&self, position: Position, piece: Piece) -> Vec<Position> {
+        self.state().preview(position, piece)
+    }
+
+    /// Plays the `Move` for the player of the current turn.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MoveError` and leaves the game unchanged if the `Move` is rejected.
+    pub fn play(&mut self, m: Move) -> Result<(), MoveError> {
+        let state = self.state().play(m)?;
+        self.update(m, state);
+        Ok(())
+    }
+
+    /// Passes the turn of the player who has no puttable square.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MoveError` and leaves the game unchanged if the player cannot pass.
+    pub fn pass(&mut self) -> Result<(), MoveError> {
+        let state = self.state().pass()?;
+        self.update(Move::Pass, state);
+        Ok(())
+    }
+
+    /// Returns the records of the moves played so far.
+    pub fn history(&self) -> &[MoveRecord] {
+        self.history.records()
+    }
+
+    /// Returns whether the last move can be taken back.
+    pub fn can_undo(&self) -> bool {
+        self.rules().takebacks && self.history.can_undo()
+    }
+
+    /// Returns whether the last undone move can be played again.
+    pub fn can_redo(&self) -> bool {
+        self.rules().takebacks && self.history.can_redo()
+    }
+
+    /// Takes back the last move.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `HistoryError` and leaves the game unchanged if takebacks are disabled
+    /// or no move has been played.
+    pub fn undo(&mut self) -> Result<(), HistoryError> {
+        if !self.rules().takebacks {
+            return Err(HistoryError::TakebacksDisabled);
+        }
+        self.history.undo().ok_or(HistoryError::NothingToUndo)?;
+        self.update_status();
+        Ok(())
+    }
+
+    /// Plays the last undone move again.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `HistoryError` and leaves the game unchanged if takebacks are disabled
+    /// or no move has been undone.
+    pub fn redo(&mut self) -> Result<(), HistoryError> {
+        if !self.rules().takebacks {
+            return Err(HistoryError::TakebacksDisabled);
+        }
+        self.history.redo().ok_or(HistoryError::NothingToRedo)?;
+        self.update_status();
+        Ok(())
+    }
+
+    /// Returns the `GameResult` if the game is finished, otherwise `None`.
+    pub fn result(&self) -> Option<GameResult> {
+        match self.status {
+            GameStatus::Finished(result) => Some(result),
+            GameStatus::InProgress => None,
+        }
+    }
+
+    /// Returns whether the game is finished.
+    pub fn is_over(&self) -> bool {
+        self.result().is_some()
+    }
+
+    fn update(&mut self, m: Move, state: GameState) {
+        let record = MoveRecord::new(m, self.state(), &state);
+        self.history.push(record, state);
+        self.update_status();
+    }
+
+    fn update_status(&mut self) {
+        self.status = match self.state().result() {
+            Some(result) => GameStatus::Finished(result),
+            None => GameStatus::InProgress,
+        };
+    }
+}
+
+
+#[cfg(test)]
+mod game_engine_test {
+    use crate::{
+        controller::{game_engine::{GameEngine, GameStatus}, history::HistoryError},
+        game::{
+            assets::{piece::Piece, piece_set::PieceSet, player::{Player, Players}, strategy_config::StrategyConfig},
+            rule::{color::Color, moves::{Move, MoveError}, position::Position, rules::Rules}
+        }
+    };
+
+    #[test]
+    fn when_engine_is_created_then_game_is_in_progress() {
+        let engine = GameEngine::new(Players::new(Player::Human, Player::Human));
+        assert_eq!(engine.status(), GameStatus::InProgress);
+        assert_eq!(engine.turn().color, Color::Black);
+        assert_eq!(engine.board().get_score().black, 2);
+    }
+
+    #[test]
+    fn when_move_is_played_then_engine_advances() {
+        let mut engine = GameEngine::new(Players::new(Player::Human, Player::Human));
+        engine.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 5))).unwrap();
+        assert_eq!(engine.turn().color, Color::White);
+        assert_eq!(engine.move_number(), 2);
+        assert_eq!(engine.pieces().black.get(&5), Some(&2));
+        assert!(engine.used().piece_set.contains(&Piece::new(Color::Black, 5)));
+    }
+
+    #[test]
+    fn when_first_legal_move_is_always_played_then_game_finishes() {
+        let mut engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        while !engine.is_over() {
+            let m = engine.legal_moves()[0];
+            engine.play(m).unwrap();
+        }
+        let result = engine.result().unwrap();
+        let score = engine.board().get_score();
+        assert_eq!(engine.status(), GameStatus::Finished(result));
+        assert_eq!((result.black_score, result.white_score), (score.black, score.white));
+        assert!(engine.legal_moves().is_empty());
+        assert_eq!(engine.play(Move::Pass), Err(MoveError::GameOver));
+    }
+
+    #[test]
+    fn when_board_is_small_then_game_finishes_on_it() {
+        let rules = Rules { board_size: 6, ..Rules::default() };
+        let mut engine = GameEngine::with_rules(Players::new(Player::RANDOM_COM, Player::RANDOM_COM), rules.clone());
+        while !engine.is_over() {
+            let m = engine.legal_moves()[0];
+            engine.play(m).unwrap();
+        }
+        assert_eq!(engine.rules(), &rules);
+        assert_eq!(engine.board().size(), 6);
+        assert_eq!(engine.move_number(), engine.history().len() + 1);
+    }
+
+    #[test]
+    fn when_piece_set_is_asymmetric_then_each_color_starts_with_its_own_pieces() {
+        let mut piece_set = PieceSet::uniform(1..=5, 6);
+        piece_set.white.insert(10, 2);
+        let rules = Rules { piece_set, ..Rules::default() };
+        let engine = GameEngine::with_rules(Players::new(Player::Human, Player::Human), rules);
+        assert_eq!(engine.pieces().black.get(&10), None);
+        assert_eq!(engine.pieces().white.get(&10), Some(&2));
+        assert!(engine.legal_moves().iter().all(|m| match m {
+            Move::Put { piece, .. } => piece.value <= 5,
+            Move::Pass => false,
+        }));
+    }
+
+    #[test]
+    fn when_move_is_undone_then_previous_state_is_restored_and_can_be_redone() {
+        let mut engine = GameEngine::new(Players::new(Player::Human, Player::Human));
+        let initial = engine.clone();
+        let m = Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 5));
+        engine.play(m).unwrap();
+        let played = engine.clone();
+        assert_eq!(engine.history()[0].played, m);
+        assert_eq!(engine.history()[0].flipped, vec![Position{x: 4, y: 3}]);
+
+        engine.undo().unwrap();
+        assert_eq!(engine.state(), initial.state());
+        assert!(engine.history().is_empty());
+        assert_eq!(engine.undo(), Err(HistoryError::NothingToUndo));
+
+        engine.redo().unwrap();
+        assert_eq!(engine, played);
+        assert_eq!(engine.redo(), Err(HistoryError::NothingToRedo));
+    }
+
+    #[test]
+    fn when_finished_game_is_undone_then_it_is_in_progress_again() {
+        let mut engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        while !engine.is_over() {
+            let m = engine.legal_moves()[0];
+            engine.play(m).unwrap();
+        }
+        engine.undo().unwrap();
+        assert_eq!(engine.status(), GameStatus::InProgress);
+    }
+
+    #[test]
+    fn when_takebacks_are_disabled_then_undo_is_rejected() {
+        let rules = Rules { takebacks: false, ..Rules::default() };
+        let mut engine = GameEngine::with_rules(Players::new(Player::Human, Player::RANDOM_COM), rules);
+        engine.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 5))).unwrap();
+        let before = engine.clone();
+        assert!(!engine.can_undo());
+        assert_eq!(engine.undo(), Err(HistoryError::TakebacksDisabled));
+        assert_eq!(engine.redo(), Err(HistoryError::TakebacksDisabled));
+        assert_eq!(engine, before);
+    }
+
+    #[test]
+    fn when_move_is_rejected_then_engine_is_unchanged() {
+        let mut engine = GameEngine::new(Players::new(Player::Human, Player::Human));
+        let before = engine.clone();
+        let result = engine.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::White, 5)));
+        assert_eq!(result, Err(MoveError::WrongTurn(Color::White)));
+        assert_eq!(engine, before);
+    }
+
+    #[test]
+    fn when_seed_is_the_same_then_random_moves_are_the_same() {
+        let engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM)).with_seed(7);
+        let moves = (0..20).scan(engine, |engine, _| {
+            let m = engine.random_move()?;
+            engine.play(m).unwrap();
+            Some(m)
+        })
+        .collect::<Vec<_>>();
+
+        let mut engine = GameEngine::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM)).with_seed(7);
+        for m in &moves[..10] {
+            assert_eq!(engine.random_move(), Some(*m));
+            engine.play(*m).unwrap();
+        }
+        // the same numbers are drawn again after the move is taken back
+        engine.undo().unwrap();
+        assert_eq!(engine.random_move(), Some(moves[9]));
+    }
+
+    #[test]
+    fn when_every_strategy_plays_then_the_game_is_finished() {
+        let strategies = [
+            StrategyConfig::Random,
+            StrategyConfig::Greedy,
+            StrategyConfig::AlphaBeta { depth: 1, node_budget: Some(200) },
+            StrategyConfig::Mcts { iterations: 2 },
+        ];
+        for config in strategies {
+            let players = Players::new(Player::Com(config), Player::Com(StrategyConfig::Random));
+            let mut engine = GameEngine::with_rules(players, Rules { board_size: 6, ..Rules::default() }).with_seed(3);
+            while let Some(m) = engine.com_move() {
+                engine.play(m).unwrap();
+            }
+            assert!(engine.is_over(), "{:?}", config);
+        }
+    }
+}
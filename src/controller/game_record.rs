@@ -0,0 +1,508 @@
+//! `GameRecord` is the text format of a whole game.
+//!
+//! A record has a header of tags with the players, the date, the seed of COM, the rules and the result,
+//! followed by a body with the moves in the notation of `Move`.
+//! A COM is written with its level, or with its `StrategyConfig` when it is not the config of a level.
+//! Passes are written in the body too, with the pieces given back as the penalty in braces.
+//!
+//! ```text
+//! [Black "Human"]
+//! [White "COM (Hard)"]
+//! [Date "2026-10-17"]
+//! [Seed "1234"]
+//! [BoardSize "8"]
+//! [BlackPieces "1x5 2x5 3x4 4x4 5x3 6x3 7x2 8x2 9x1 10x1"]
+//! [WhitePieces "1x5 2x5 3x4 4x4 5x3 6x3 7x2 8x2 9x1 10x1"]
+//! [FlipRule "StrictSum"]
+//! [Takebacks "true"]
+//! [Result "*"]
+//!
+//! 1. B7e3 W1d3
+//! 2. B2c4 pass {+B7}
+//! ```
+//!
+//! # Examples
+//!
+//! ```
+//! use number_reversi::controller::{game_engine::GameEngine, game_record::GameRecord};
+//! use number_reversi::game::assets::player::{Player, Players};
+//!
+//! let mut engine = GameEngine::new(Players::new(Player::Human, Player::RANDOM_COM));
+//! engine.play("B7e3".parse().unwrap()).unwrap();
+//! engine.play("W1d3".parse().unwrap()).unwrap();
+//!
+//! let text = GameRecord::from_engine(&engine, "2026-10-17").to_string();
+//! let loaded = text.parse::<GameRecord>().unwrap().replay().unwrap();
+//! assert_eq!(loaded.state(), engine.state());
+//! ```
+
+use std::{collections::BTreeMap, error::Error, fmt, str::FromStr};
+
+use crate::game::{
+    assets::{board::SIZES, level::Level, piece::Piece, piece_set::PieceSet, player::{Player, Players}},
+    rule::{
+        color::Color,
+        flip_rule::BuiltinFlipRule,
+        moves::{Move, MoveError, ParseMoveError},
+        rules::Rules,
+        score::Score
+    }
+};
+
+use super::game_engine::GameEngine;
+
+/// A move in the body of a `GameRecord`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RecordedMove {
+    pub played: Move,
+    /// The pieces given back to the opponent by a pass. Always empty for a `Move::Put`.
+    pub penalties: Vec<Piece>,
+}
+
+/// `GameRecord` holds everything needed to write down a game and replay it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GameRecord {
+    pub players: Players,
+    pub rules: Rules,
+    /// The date the game was played, such as `2026-10-17`.
+    pub date: String,
+    /// The seed of the random choices of COM, or `None` if the record does not have one.
+    pub seed: Option<u64>,
+    /// The final score, or `None` if the game is not finished.
+    pub result: Option<Score>,
+    /// The moves of both players in turn, starting from black.
+    pub moves: Vec<RecordedMove>,
+}
+impl GameRecord {
+    /// Creates a `GameRecord` of the moves played in the `GameEngine`.
+    pub fn from_engine(engine: &GameEngine, date: &str) -> Self {
+        let moves = engine.history().iter().flat_map(|record| {
+            let mut moves = vec![];
+            match record.played {
+                Move::Put { .. } => {
+                    moves.push(RecordedMove { played: record.played, penalties: vec![] });
+                    if record.passed {
+                        moves.push(RecordedMove { played: Move::Pass, penalties: record.penalties.clone() });
+                    }
+                },
+                Move::Pass => moves.push(RecordedMove { played: Move::Pass, penalties: record.penalties.clone() }),
+            }
+            moves
+        })
+        .collect();
+        Self {
+            players: engine.state().players,
+            rules: engine.rules().clone(),
+            date: date.to_string(),
+            seed: Some(engine.seed()),
+            result: engine.result().map(|result| Score { black: result.black_score, white: result.white_score }),
+            moves,
+        }
+    }
+
+    /// Replays the moves into a new `GameEngine`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `RecordError` at the first move that does not follow the rules,
+    /// or if the result does not match the replayed game.
+    pub fn replay(&self) -> Result<GameEngine, RecordError> {
+        let mut engine = GameEngine::with_rules(self.players, self.rules.clone());
+        if let Some(seed) = self.seed {
+            engine = engine.with_seed(seed);
+        }
+        // whether the last move made the opponent pass, so the next entry must be that pass
+        let mut pending_pass = false;
+        for (i, recorded) in self.moves.iter().enumerate() {
+            let ply = i + 1;
+            let illegal = |error| RecordError::IllegalMove { ply, played: recorded.played, error };
+            match recorded.played {
+                Move::Put { .. } if pending_pass => return Err(RecordError::MissingPass { ply }),
+                Move::Put { .. } => {
+                    engine.play(recorded.played).map_err(illegal)?;
+                    pending_pass = engine.history().last().is_some_and(|record| record.passed);
+                },
+                Move::Pass => {
+                    if !pending_pass {
+                        engine.pass().map_err(illegal)?;
+                    }
+                    pending_pass = false;
+                    let penalties = engine.history().last().map(|record| record.penalties.clone()).unwrap_or_default();
+                    if penalties != recorded.penalties {
+                        return Err(RecordError::PenaltyMismatch { ply, expected: penalties, actual: recorded.penalties.clone() });
+                    }
+                },
+            }
+        }
+        if pending_pass {
+            return Err(RecordError::MissingPass { ply: self.moves.len() + 1 });
+        }
+        let result = engine.result().map(|result| Score { black: result.black_score, white: result.white_score });
+        if result != self.result {
+            return Err(RecordError::ResultMismatch { expected: result, actual: self.result });
+        }
+        Ok(engine)
+    }
+}
+
+impl fmt::Display for GameRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "[Black \"{}\"]", self.players.black)?;
+        writeln!(f, "[White \"{}\"]", self.players.white)?;
+        writeln!(f, "[Date \"{}\"]", self.date)?;
+        if let Some(seed) = self.seed {
+            writeln!(f, "[Seed \"{}\"]", seed)?;
+        }
+        writeln!(f, "[BoardSize \"{}\"]", self.rules.board_size)?;
+        writeln!(f, "[BlackPieces \"{}\"]", write_pieces(&self.rules.piece_set.black))?;
+        writeln!(f, "[WhitePieces \"{}\"]", write_pieces(&self.rules.piece_set.white))?;
+        writeln!(f, "[FlipRule \"{:?}\"]", self.rules.flip_rule)?;
+        writeln!(f, "[Takebacks \"{}\"]", self.rules.takebacks)?;
+        match self.result {
+            Some(score) => writeln!(f, "[Result \"{}-{}\"]", score.black, score.white)?,
+            None => writeln!(f, "[Result \"*\"]")?,
+        }
+        writeln!(f)?;
+        for (i, pair) in self.moves.chunks(2).enumerate() {
+            let moves = pair.iter().map(|recorded| match recorded.played {
+                Move::Pass if !recorded.penalties.is_empty() => {
+                    let penalties = recorded.penalties.iter()
+                        .map(|piece| format!("+{}", write_piece(*piece)))
+                        .collect::<Vec<_>>()
+                        .join(",");
+                    format!("pass {{{}}}", penalties)
+                },
+                m => m.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" ");
+            writeln!(f, "{}. {}", i + 1, moves)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for GameRecord {
+    type Err = RecordError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tags = BTreeMap::new();
+        let mut body = vec![];
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if line.starts_with('[') {
+                let (name, value) = parse_tag(line).ok_or_else(|| RecordError::InvalidHeader(line.to_string()))?;
+                tags.insert(name, value);
+            } else {
+                body.push(line);
+            }
+        }
+
+        let tag = |name: &str| tags.get(name).map(String::as_str);
+        let invalid = |name: &str| RecordError::InvalidHeader(format!("{} \"{}\"", name, tag(name).unwrap_or_default()));
+        let player = |name: &str| tag(name).and_then(parse_player).ok_or_else(|| invalid(name));
+        let players = Players::new(player("Black")?, player("White")?);
+
+        let default = Rules::default();
+        let rules = Rules {
+            board_size: match tag("BoardSize") {
+                Some(size) => size.parse().ok()
+                    .filter(|size| SIZES.contains(size))
+                    .ok_or_else(|| invalid("BoardSize"))?,
+                None => default.board_size,
+            },
+            piece_set: PieceSet {
+                black: match tag("BlackPieces") {
+                    Some(pieces) => parse_pieces(pieces).ok_or_else(|| invalid("BlackPieces"))?,
+                    None => default.piece_set.black,
+                },
+                white: match tag("WhitePieces") {
+                    Some(pieces) => parse_pieces(pieces).ok_or_else(|| invalid("WhitePieces"))?,
+                    None => default.piece_set.white,
+                },
+            },
+            flip_rule: match tag("FlipRule") {
+                Some(name) => BuiltinFlipRule::ALL.into_iter()
+                    .find(|rule| format!("{:?}", rule) == name)
+                    .ok_or_else(|| invalid("FlipRule"))?,
+                None => default.flip_rule,
+            },
+            takebacks: match tag("Takebacks") {
+                Some(takebacks) => takebacks.parse().map_err(|_| invalid("Takebacks"))?,
+                None => default.takebacks,
+            },
+        };
+        let result = match tag("Result") {
+            None | Some("*") => None,
+            Some(result) => {
+                let (black, white) = result.split_once('-').ok_or_else(|| invalid("Result"))?;
+                Some(Score {
+                    black: black.parse().map_err(|_| invalid("Result"))?,
+                    white: white.parse().map_err(|_| invalid("Result"))?,
+                })
+            },
+        };
+
+        let mut moves: Vec<RecordedMove> = vec![];
+        for token in body.iter().flat_map(|line| line.split_whitespace()) {
+            if token.ends_with('.') && token[..token.len() - 1].chars().all(|c| c.is_ascii_digit()) {
+                continue;
+            }
+            let ply = moves.len() + 1;
+            if let Some(penalties) = token.strip_prefix('{').and_then(|t| t.strip_suffix('}')) {
+                let invalid_penalty = || RecordError::InvalidPenalty { ply: ply - 1, text: token.to_string() };
+                let last = moves.last_mut()
+                    .filter(|recorded| recorded.played == Move::Pass)
+                    .ok_or_else(invalid_penalty)?;
+                last.penalties = penalties.split(',')
+                    .map(|piece| piece.strip_prefix('+').and_then(parse_piece))
+                    .collect::<Option<Vec<_>>>()
+                    .ok_or_else(invalid_penalty)?;
+                continue;
+            }
+            let played = token.parse::<Move>()
+                .map_err(|error| RecordError::InvalidMove { ply, text: token.to_string(), error })?;
+            moves.push(RecordedMove { played, penalties: vec![] });
+        }
+
+        Ok(Self {
+            players,
+            rules,
+            date: tag("Date").unwrap_or_default().to_string(),
+            seed: match tag("Seed") {
+                Some(seed) => Some(seed.parse().map_err(|_| invalid("Seed"))?),
+                None => None,
+            },
+            result,
+            moves,
+        })
+    }
+}
+
+/// Represents why a `GameRecord` cannot be read or replayed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordError {
+    /// A tag of the header is malformed or has an invalid value.
+    InvalidHeader(String),
+    /// The move at `ply` is not written in the notation.
+    InvalidMove { ply: usize, text: String, error: ParseMoveError },
+    /// The penalty after the move at `ply` is malformed, or does not follow a pass.
+    InvalidPenalty { ply: usize, text: String },
+    /// The move at `ply` is rejected by the rules.
+    IllegalMove { ply: usize, played: Move, error: MoveError },
+    /// The player to move at `ply` cannot put a piece, so the move must be a pass.
+    MissingPass { ply: usize },
+    /// The penalty of the pass at `ply` is not the one given by the rules.
+    PenaltyMismatch { ply: usize, expected: Vec<Piece>, actual: Vec<Piece> },
+    /// The result in the header is not the result of the replayed game.
+    ResultMismatch { expected: Option<Score>, actual: Option<Score> },
+}
+impl fmt::Display for RecordError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pieces = |pieces: &Vec<Piece>| pieces.iter().map(|p| write_piece(*p)).collect::<Vec<_>>().join(",");
+        let score = |score: &Option<Score>| match score {
+            Some(score) => format!("{}-{}", score.black, score.white),
+            None => "*".to_string(),
+        };
+        match self {
+            RecordError::InvalidHeader(tag) => write!(f, "invalid header: {}", tag),
+            RecordError::InvalidMove { ply, text, error } => write!(f, "move {} ({}): {}", ply, text, error),
+            RecordError::InvalidPenalty { ply, text } => write!(f, "move {}: invalid penalty {}", ply, text),
+            RecordError::IllegalMove { ply, played, error } => write!(f, "move {} ({}): {}", ply, played, error),
+            RecordError::MissingPass { ply } => write!(f, "move {}: the player cannot put a piece and must pass", ply),
+            RecordError::PenaltyMismatch { ply, expected, actual } =>
+                write!(f, "move {} (pass): the penalty is {{{}}}, not {{{}}}", ply, pieces(expected), pieces(actual)),
+            RecordError::ResultMismatch { expected, actual } =>
+                write!(f, "the result is {}, not {}", score(expected), score(actual)),
+        }
+    }
+}
+impl Error for RecordError {}
+
+fn parse_player(name: &str) -> Option<Player> {
+    match name {
+        "Human" => Some(Player::Human),
+        "COM" => Some(Player::RANDOM_COM),
+        _ => {
+            let strategy = name.strip_prefix("COM (")?.strip_suffix(')')?;
+            match Level::ALL.into_iter().find(|l| l.name() == strategy) {
+                Some(level) => Some(Player::Com(level.config())),
+                None => strategy.parse().ok().map(Player::Com),
+            }
+        },
+    }
+}
+
+fn write_piece(piece: Piece) -> String {
+    match piece.color {
+        Color::Black => format!("B{}", piece.value),
+        Color::White => format!("W{}", piece.value),
+    }
+}
+
+fn parse_piece(s: &str) -> Option<Piece> {
+    let color = match s.chars().next()? {
+        'B' => Color::Black,
+        'W' => Color::White,
+        _ => return None,
+    };
+    Some(Piece::new(color, s[1..].parse().ok()?))
+}
+
+/// Writes the pieces as `value x count`, such as `1x5 2x5`.
+fn write_pieces(pieces: &BTreeMap<u8, u8>) -> String {
+    pieces.iter().map(|(v, n)| format!("{}x{}", v, n)).collect::<Vec<_>>().join(" ")
+}
+
+fn parse_pieces(s: &str) -> Option<BTreeMap<u8, u8>> {
+    s.split_whitespace().map(|entry| {
+        let (value, count) = entry.split_once('x')?;
+        Some((value.parse().ok()?, count.parse().ok()?))
+    })
+    .collect()
+}
+
+/// Parses a tag such as `[Black "Human"]` into its name and value.
+fn parse_tag(line: &str) -> Option<(String, String)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (name, value) = inner.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((name.to_string(), value.to_string()))
+}
+
+
+#[cfg(test)]
+mod game_record_test {
+    use crate::{
+        controller::{
+            game_engine::GameEngine,
+            game_record::{GameRecord, RecordError, RecordedMove},
+            game_state::GameState
+        },
+        game::{
+            assets::{level::Level, piece::{Piece, UsedPiece}, piece_set::PieceSet, player::{Player, Players}, strategy_config::StrategyConfig},
+            rule::{
+                color::Color, flip_rule::BuiltinFlipRule, moves::{Move, MoveError}, position::Position,
+                rules::Rules
+            }
+        },
+        tests::helper::board_helper::BoardHelper
+    };
+
+    fn finished_engine(rules: Rules) -> GameEngine {
+        let players = Players::new(Player::Com(Level::Hard.config()), Player::Human);
+        let mut engine = GameEngine::with_rules(players, rules);
+        let mut i = 0;
+        while !engine.is_over() {
+            let moves = engine.legal_moves();
+            engine.play(moves[i * 7 % moves.len()]).unwrap();
+            i += 1;
+        }
+        engine
+    }
+
+    #[test]
+    fn when_finished_game_is_saved_then_it_is_replayed_to_the_same_result() {
+        let mut piece_set = PieceSet::uniform(1..=5, 6);
+        piece_set.white.insert(9, 1);
+        let rules = Rules { board_size: 6, piece_set, flip_rule: BuiltinFlipRule::Product, takebacks: false };
+        let engine = finished_engine(rules);
+
+        let record = GameRecord::from_engine(&engine, "2026-10-17");
+        let text = record.to_string();
+        let parsed = text.parse::<GameRecord>().unwrap();
+        assert_eq!(parsed, record);
+
+        let replayed = parsed.replay().unwrap();
+        assert_eq!(replayed, engine.clone().with_seed(replayed.seed()));
+        assert_eq!(replayed.state(), engine.state());
+        assert_eq!(replayed.result(), engine.result());
+        assert!(text.contains("[Date \"2026-10-17\"]"));
+        assert!(text.contains("[FlipRule \"Product\"]"));
+        assert!(text.contains("[Black \"COM (Hard)\"]"));
+    }
+
+    #[test]
+    fn when_com_is_not_of_a_level_then_its_strategy_is_saved() {
+        let players = Players::new(
+            Player::Com(StrategyConfig::AlphaBeta { depth: 4, node_budget: None }),
+            Player::Com(StrategyConfig::Mcts { iterations: 300 }),
+        );
+        let engine = GameEngine::new(players);
+
+        let text = GameRecord::from_engine(&engine, "2026-10-17").to_string();
+        assert!(text.contains("[Black \"COM (AlphaBeta 4)\"]"));
+        assert!(text.contains("[White \"COM (Mcts 300)\"]"));
+        assert_eq!(text.parse::<GameRecord>().unwrap().players, players);
+    }
+
+    #[test]
+    fn when_opponent_has_to_pass_then_pass_and_penalty_are_written() {
+        let board = BoardHelper::make_board(vec![(0, 0, 1)], vec![(1, 0, 1), (0, 1, 1)]);
+        let state = GameState {
+            board,
+            used: UsedPiece::new().add_piece(Piece::new(Color::Black, 7)),
+            ..GameState::new(Players::new(Player::Human, Player::Human))
+        };
+        let mut engine = GameEngine::from_state(state);
+        engine.play(Move::put(Position{x: 2, y: 0}, Piece::new(Color::Black, 1))).unwrap();
+
+        let record = GameRecord::from_engine(&engine, "");
+        assert_eq!(record.moves[1], RecordedMove { played: Move::Pass, penalties: vec![Piece::new(Color::Black, 7)] });
+        assert!(record.to_string().contains("1. B1c1 pass {+B7}"));
+    }
+
+    #[test]
+    fn when_move_is_illegal_then_error_points_at_it() {
+        let text = "[Black \"Human\"]\n[White \"Human\"]\n\n1. B7e3 W1d3\n2. B2a1 W3c3\n";
+        let error = text.parse::<GameRecord>().unwrap().replay().unwrap_err();
+        assert_eq!(error, RecordError::IllegalMove {
+            ply: 3,
+            played: Move::put(Position{x: 0, y: 0}, Piece::new(Color::Black, 2)),
+            error: MoveError::SquareNotPuttable(Position{x: 0, y: 0}),
+        });
+        assert_eq!(error.to_string(), "move 3 (B2a1): cannot put a piece on a1");
+    }
+
+    #[test]
+    fn when_move_is_not_in_notation_then_error_points_at_it() {
+        let text = "[Black \"Human\"]\n[White \"Human\"]\n\n1. B7e3 W1d9x\n";
+        assert!(matches!(text.parse::<GameRecord>(), Err(RecordError::InvalidMove { ply: 2, .. })));
+    }
+
+    #[test]
+    fn when_pass_is_missing_or_not_allowed_then_returns_error() {
+        let text = "[Black \"Human\"]\n[White \"Human\"]\n\n1. pass\n";
+        let error = text.parse::<GameRecord>().unwrap().replay().unwrap_err();
+        assert_eq!(error, RecordError::IllegalMove { ply: 1, played: Move::Pass, error: MoveError::PassNotAllowed });
+
+        let engine = finished_engine(Rules::default());
+        let mut record = GameRecord::from_engine(&engine, "");
+        let i = record.moves.iter().position(|recorded| recorded.played == Move::Pass).unwrap();
+        record.moves.remove(i);
+        assert!(matches!(record.replay(), Err(RecordError::MissingPass { ply }) if ply == i + 1));
+    }
+
+    #[test]
+    fn when_result_is_tampered_then_returns_error() {
+        let engine = finished_engine(Rules::default());
+        let mut record = GameRecord::from_engine(&engine, "");
+        record.result = None;
+        assert!(matches!(record.replay(), Err(RecordError::ResultMismatch { actual: None, .. })));
+    }
+
+    #[test]
+    fn when_header_is_invalid_then_returns_error() {
+        assert_eq!("[Black \"Human\"]\n[White \"Alien\"]\n".parse::<GameRecord>(),
+            Err(RecordError::InvalidHeader("White \"Alien\"".to_string())));
+        assert_eq!("[Black \"Human\"]\n[White \"COM\"]\n[BoardSize \"7\"]\n".parse::<GameRecord>(),
+            Err(RecordError::InvalidHeader("BoardSize \"7\"".to_string())));
+        assert_eq!("[Black \"Human\"]\n[White \"COM\"]\n[BoardSize \"4\"]\n".parse::<GameRecord>(),
+            Err(RecordError::InvalidHeader("BoardSize \"4\"".to_string())));
+        assert_eq!("[Black \"Human\"]\n[White \"COM\"]\n[BoardSize \"1000\"]\n".parse::<GameRecord>(),
+            Err(RecordError::InvalidHeader("BoardSize \"1000\"".to_string())));
+        assert_eq!("[Black \"COM (Genius)\"]\n[White \"COM\"]\n".parse::<GameRecord>(),
+            Err(RecordError::InvalidHeader("Black \"COM (Genius)\"".to_string())));
+        assert_eq!("[Black Human]\n".parse::<GameRecord>(),
+            Err(RecordError::InvalidHeader("[Black Human]".to_string())));
+    }
+}
@@ -1,17 +1,318 @@
-use crate::game::rule::turn::Turn;
-use yew::UseStateHandle;
+use serde::{Deserialize, Serialize};
 
-use crate::game::assets::{
-    board::Board, 
-    piece::Piece, 
-    pieces::Pieces
+use crate::game::{
+    assets::{
+        board::Board,
+        piece::{Piece, UsedPiece},
+        pieces::Pieces,
+        player::Players
+    },
+    events::{
+        check_game_over::check_game_over,
+        check_pass::check_pass,
+        put_piece::put_piece,
+        reverse::flipped_positions
+    },
+    rule::{color::Color, legal_moves::LegalMoves, moves::{Move, MoveError}, position::Position, result::GameResult, rules::Rules, turn::Turn}
 };
 
-
-#[derive(Debug, Clone)]
+/// `GameState` is a snapshot of everything needed to continue a game.
+///
+/// It has no dependency on Yew, so it can be copied, inspected and played
+/// forward by bots, tests and tools outside the browser.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct GameState {
-    pub board: UseStateHandle<Board>,
-    pub piece: UseStateHandle<Piece>,
-    pub pieces: UseStateHandle<Pieces>,
-    pub turn: UseStateHandle<Turn>,
-}
\ No newline at end of file
+    pub board: Board,
+    pub pieces: Pieces,
+    pub used: UsedPiece,
+    pub turn: Turn,
+    pub players: Players,
+    pub rules: Rules,
+    /// The number of the next move, starting from 1.
+    /// A pass counts as a move as a put does, so it is one more than the number of moves in the history.
+    pub move_number: usize,
+}
+impl GameState {
+    /// Creates a new `GameState` at the start of a game with the default `Rules`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_state::GameState;
+    /// use number_reversi::game::assets::player::{Player, Players};
+    /// use number_reversi::game::rule::color::Color;
+    ///
+    /// let state = GameState::new(Players::new(Player::Human, Player::RANDOM_COM));
+    /// assert_eq!(state.turn.color, Color::Black);
+    /// assert_eq!(state.move_number, 1);
+    /// ```
+    pub fn new(players: Players) -> Self {
+        Self::with_rules(players, Rules::default())
+    }
+
+    /// Creates a new `GameState` at the start of a game played with the `Rules`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_state::GameState;
+    /// use number_reversi::game::assets::player::{Player, Players};
+    /// use number_reversi::game::rule::rules::Rules;
+    ///
+    /// let rules = Rules { board_size: 6, ..Rules::default() };
+    /// let state = GameState::with_rules(Players::new(Player::Human, Player::RANDOM_COM), rules);
+    /// assert_eq!(state.board.size(), 6);
+    /// ```
+    pub fn with_rules(players: Players, rules: Rules) -> Self {
+        let turn = Turn{color: Color::Black, player: players.black};
+        Self {
+            board: Board::with_size(rules.board_size),
+            pieces: Pieces::from_set(&rules.piece_set),
+            used: UsedPiece::new(),
+            turn,
+            players,
+            rules,
+            move_number: 1,
+        }
+    }
+
+    /// Returns every `Move` the player of the current turn can make.
+    ///
+    /// A `Move::Put` is returned for each puttable square combined with each value
+    /// the player still has. If the player cannot put anywhere but the opponent can,
+    /// `Move::Pass` is the only move. The result is empty when the game is over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_state::GameState;
+    /// use number_reversi::game::assets::player::{Player, Players};
+    ///
+    /// let state = GameState::new(Players::new(Player::Human, Player::Human));
+    /// // 4 puttable squares and 10 values
+    /// assert_eq!(state.legal_moves().len(), 40);
+    /// ```
+    pub fn legal_moves(&self) -> Vec<Move> {
+        if self.is_over() {
+            return vec![];
+        }
+        let moves = self.put_moves();
+        if moves.is_empty() {
+            vec![Move::Pass]
+        } else {
+            moves
+        }
+    }
+
+    fn put_moves(&self) -> Vec<Move> {
+        let mut values = match self.turn.color {
+            Color::Black => self.pieces.black.iter(),
+            Color::White => self.pieces.white.iter(),
+        }
+        .filter(|(_, n)| **n > 0)
+        .map(|(v, _)| *v)
+        .collect::<Vec<u8>>();
+        values.sort();
+
+        self.legal_positions().positions().into_iter().flat_map(|position| {
+            values.iter().map(move |v| Move::put(position, Piece::new(self.turn.color, *v)))
+        })
+        .collect()
+    }
+
+    /// Returns the puttable positions of the current turn with their `ReversibleCandidates`.
+    pub fn legal_positions(&self) -> LegalMoves {
+        LegalMoves::new(&self.board, self.turn)
+    }
+
+    /// Returns the positions of the pieces that would be reversed if the `Piece` were put on the `Position`,
+    /// following the `FlipRule` of the game. The result is empty if the piece cannot be put there.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_state::GameState;
+    /// use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+    /// use number_reversi::game::rule::{color::Color, position::Position};
+    ///
+    /// let state = GameState::new(Players::new(Player::Human, Player::Human));
+    /// let piece = Piece::new(Color::Black, 1);
+    /// assert_eq!(state.preview(Position{x: 4, y: 2}, piece), vec![Position{x: 4, y: 3}]);
+    /// assert_eq!(state.preview(Position{x: 0, y: 0}, piece), vec![]);
+    /// ```
+    pub fn preview(&self, position: Position, piece: Piece) -> Vec<Position> {
+        match self.legal_positions().get(position) {
+            Some(candidates) => flipped_positions(&self.board, piece, candidates, &self.rules.flip_rule),
+            None => vec![],
+        }
+    }
+
+    /// Returns the `GameState` after the `Move` is played.
+    ///
+    /// A `Move::Put` goes through `put_piece`, so the turn is passed automatically
+    /// when the opponent cannot put anywhere.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `MoveError` describing why the `Move` is rejected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::controller::game_state::GameState;
+    /// use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+    /// use number_reversi::game::rule::{color::Color, moves::{Move, MoveError}, position::Position};
+    ///
+    /// let state = GameState::new(Players::new(Player::Human, Player::Human));
+    /// let state = state.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 3))).unwrap();
+    /// assert_eq!(state.turn.color, Color::White);
+    /// assert_eq!(state.move_number, 2);
+    ///
+    /// let result = state.play(Move::put(Position{x: 0, y: 0}, Piece::new(Color::White, 3)));
+    /// assert_eq!(result, Err(MoveError::SquareNotPuttable(Position{x: 0, y: 0})));
+    /// ```
+    pub fn play(&self, m: Move) -> Result<Self, MoveError> {
+        if self.is_over() {
+            return Err(MoveError::GameOver);
+        }
+        match m {
+            Move::Put { position, piece } => {
+                let (board, turn, pieces, used) = put_piece(
+                    position, piece, self.board.clone(), self.turn,
+                    self.pieces.clone(), self.used.clone(), self.players, &self.rules.flip_rule
+                )?;
+                Ok(Self { board, pieces, used, turn, move_number: self.move_number + 1, ..self.clone() })
+            },
+            Move::Pass => self.pass(),
+        }
+    }
+
+    /// Returns the `GameState` after the player of the current turn passes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `MoveError::PassNotAllowed` if the player can put a piece.
+    pub fn pass(&self) -> Result<Self, MoveError> {
+        if self.legal_moves() != vec![Move::Pass] {
+            return Err(if self.is_over() { MoveError::GameOver } else { MoveError::PassNotAllowed });
+        }
+        let (turn, used, pieces) = check_pass(
+            self.turn, &self.board, self.used.clone(), self.pieces.clone(), self.players
+        );
+        Ok(Self { pieces, used, turn, move_number: self.move_number + 1, ..self.clone() })
+    }
+
+    /// Returns the `GameResult` if the game has ended, otherwise `None`.
+    pub fn result(&self) -> Option<GameResult> {
+        check_game_over(&self.board, self.turn, &self.pieces, self.players)
+    }
+
+    /// Returns whether the game has ended.
+    pub fn is_over(&self) -> bool {
+        self.result().is_some()
+    }
+}
+
+
+#[cfg(test)]
+mod game_state_test {
+    use crate::{
+        controller::game_state::GameState,
+        game::{
+            assets::{piece::{Piece, UsedPiece}, player::{Player, Players}, square::Square},
+            rule::{
+                color::Color, flip_rule::BuiltinFlipRule, moves::{Move, MoveError}, position::Position,
+                result::EndReason, rules::Rules, turn::Turn
+            }
+        },
+        tests::helper::board_helper::BoardHelper
+    };
+
+    fn human_players() -> Players {
+        Players::new(Player::Human, Player::Human)
+    }
+
+    #[test]
+    fn when_game_starts_then_black_can_put_on_4_squares() {
+        let state = GameState::new(human_players());
+        let positions = state.legal_moves().into_iter().filter_map(|m| match m {
+            Move::Put { position, piece } if piece.value == 1 => Some(position),
+            _ => None,
+        }).collect::<Vec<_>>();
+        assert_eq!(positions, vec![
+            Position{x: 2, y: 4}, Position{x: 3, y: 5}, Position{x: 4, y: 2}, Position{x: 5, y: 3}
+        ]);
+    }
+
+    #[test]
+    fn when_put_then_piece_is_consumed_and_turn_changes() {
+        let state = GameState::new(human_players());
+        let piece = Piece::new(Color::Black, 10);
+        let state = state.play(Move::put(Position{x: 4, y: 2}, piece)).unwrap();
+        assert_eq!(state.board.squares[4][2], Square::Put(piece));
+        assert_eq!(state.board.squares[4][3], Square::Put(Piece::new(Color::Black, 1)));
+        assert_eq!(state.pieces.black.get(&10), Some(&0));
+        assert_eq!(state.turn, Turn{color: Color::White, player: Player::Human});
+    }
+
+    #[test]
+    fn when_value_is_used_up_then_it_is_not_a_legal_move() {
+        let state = GameState::new(human_players());
+        let state = state.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 10))).unwrap();
+        let state = state.play(Move::put(Position{x: 3, y: 2}, Piece::new(Color::White, 1))).unwrap();
+        assert!(state.legal_moves().iter().all(|m| match m {
+            Move::Put { piece, .. } => piece.value != 10,
+            Move::Pass => false,
+        }));
+    }
+
+    #[test]
+    fn when_move_is_not_legal_then_returns_error() {
+        let state = GameState::new(human_players());
+        let result = state.play(Move::put(Position{x: 0, y: 0}, Piece::new(Color::Black, 1)));
+        assert_eq!(result, Err(MoveError::SquareNotPuttable(Position{x: 0, y: 0})));
+        assert_eq!(state.pass(), Err(MoveError::PassNotAllowed));
+    }
+
+    #[test]
+    fn when_flip_rule_is_chosen_then_preview_and_play_follow_it() {
+        let board = BoardHelper::make_board(vec![(0, 0, 1)], vec![(1, 0, 10)]);
+        let position = Position{x: 2, y: 0};
+        let piece = Piece::new(Color::Black, 1);
+        for (flip_rule, flipped) in [(BuiltinFlipRule::StrictSum, false), (BuiltinFlipRule::AlwaysFlip, true)] {
+            let rules = Rules { flip_rule, ..Rules::default() };
+            let state = GameState { board: board.clone(), ..GameState::with_rules(human_players(), rules) };
+            let preview = state.preview(position, piece);
+            let next = state.play(Move::put(position, piece)).unwrap();
+            assert_eq!(preview.contains(&Position{x: 1, y: 0}), flipped);
+            assert_eq!(next.board.squares[1][0] == Square::Put(Piece::new(Color::Black, 10)), flipped);
+        }
+    }
+
+    #[test]
+    fn when_only_opponent_can_put_then_pass_is_the_only_move() {
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let state = GameState {
+            board,
+            used: UsedPiece::new().add_piece(Piece::new(Color::White, 1)),
+            ..GameState::new(human_players())
+        };
+        assert_eq!(state.legal_moves(), vec![Move::Pass]);
+        let passed = state.pass().unwrap();
+        assert_eq!(passed.turn.color, Color::White);
+        assert_eq!(passed.move_number, state.move_number + 1);
+    }
+
+    #[test]
+    fn when_nobody_can_put_then_game_is_over() {
+        let board = BoardHelper::make_board(vec![(0, 0, 1)], vec![(7, 7, 1)]);
+        let state = GameState {
+            board,
+            ..GameState::new(human_players())
+        };
+        assert!(state.legal_moves().is_empty());
+        assert!(state.is_over());
+        assert_eq!(state.result().map(|r| r.reason), Some(EndReason::NoMoves));
+        assert_eq!(state.play(Move::Pass), Err(MoveError::GameOver));
+    }
+}
@@ -0,0 +1,225 @@
+use std::{error::Error, fmt};
+
+use crate::game::{
+    assets::{piece::{Piece, UsedPiece}, pieces::Pieces},
+    rule::{color::Color, moves::Move, position::Position}
+};
+
+use super::game_state::GameState;
+
+/// `MoveRecord` describes a `Move` that has been played.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MoveRecord {
+    /// The move that was played.
+    pub played: Move,
+    /// The color of the player who played the move.
+    pub color: Color,
+    /// The positions of the pieces reversed by the move.
+    pub flipped: Vec<Position>,
+    /// Whether the opponent had to pass after the move, so the same player moves again.
+    pub passed: bool,
+    /// The pieces given back by the passes that followed the move, at most one of each value.
+    pub penalties: Vec<Piece>,
+    /// The remaining pieces after the move.
+    pub pieces: Pieces,
+    /// The used pieces after the move.
+    pub used: UsedPiece,
+}
+impl MoveRecord {
+    /// Creates a `MoveRecord` of the `Move` that turned `before` into `after`.
+    pub fn new(played: Move, before: &GameState, after: &GameState) -> Self {
+        let flipped = match played {
+            Move::Put { position, piece } => before.preview(position, piece),
+            Move::Pass => vec![],
+        };
+        let mut used = before.used.clone();
+        if let Move::Put { piece, .. } = played {
+            used = used.add_piece(piece);
+        }
+        let mut penalties = used.piece_set.difference(&after.used.piece_set).copied().collect::<Vec<_>>();
+        penalties.sort_by_key(|piece| (piece.color == Color::White, piece.value));
+        Self {
+            played,
+            color: before.turn.color,
+            flipped,
+            passed: matches!(played, Move::Put { .. }) && after.turn.color == before.turn.color,
+            penalties,
+            pieces: after.pieces.clone(),
+            used: after.used.clone(),
+        }
+    }
+}
+
+/// The reasons why the history cannot be moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryError {
+    /// Takebacks are disabled by the rules of the game.
+    TakebacksDisabled,
+    /// No move has been played yet.
+    NothingToUndo,
+    /// No move has been undone since the last move.
+    NothingToRedo,
+}
+impl fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HistoryError::TakebacksDisabled => write!(f, "takebacks are disabled in this game"),
+            HistoryError::NothingToUndo => write!(f, "there is no move to undo"),
+            HistoryError::NothingToRedo => write!(f, "there is no move to redo"),
+        }
+    }
+}
+impl Error for HistoryError {}
+
+/// `History` keeps every `GameState` of a game, so the moves can be undone and redone.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::controller::{game_state::GameState, history::{History, MoveRecord}};
+/// use number_reversi::game::assets::{piece::Piece, player::{Player, Players}};
+/// use number_reversi::game::rule::{color::Color, moves::Move, position::Position};
+///
+/// let initial = GameState::new(Players::new(Player::Human, Player::Human));
+/// let mut history = History::new(initial.clone());
+///
+/// let m = Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 1));
+/// let next = initial.play(m).unwrap();
+/// history.push(MoveRecord::new(m, &initial, &next), next.clone());
+///
+/// assert_eq!(history.undo().map(|record| record.played), Some(m));
+/// assert_eq!(history.current(), &initial);
+/// assert!(history.redo().is_some());
+/// assert_eq!(history.current(), &next);
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct History {
+    /// The states from the start of the game. `states[i + 1]` is the state after `records[i]`.
+    states: Vec<GameState>,
+    records: Vec<MoveRecord>,
+    /// The number of moves played to reach the current state.
+    cursor: usize,
+}
+impl History {
+    /// Creates a new `History` starting from the `GameState`.
+    pub fn new(initial: GameState) -> Self {
+        Self { states: vec![initial], records: vec![], cursor: 0 }
+    }
+
+    /// Returns the current `GameState`.
+    pub fn current(&self) -> &GameState {
+        &self.states[self.cursor]
+    }
+
+    /// Returns the records of the moves played to reach the current state.
+    pub fn records(&self) -> &[MoveRecord] {
+        &self.records[..self.cursor]
+    }
+
+    /// Adds the `MoveRecord` and the `GameState` after it.
+    /// The moves undone before are discarded, so they can no longer be redone.
+    pub fn push(&mut self, record: MoveRecord, state: GameState) {
+        self.states.truncate(self.cursor + 1);
+        self.records.truncate(self.cursor);
+        self.states.push(state);
+        self.records.push(record);
+        self.cursor += 1;
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.cursor > 0
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.cursor < self.records.len()
+    }
+
+    /// Goes back to the state before the last move, and returns the record of the undone move.
+    pub fn undo(&mut self) -> Option<&MoveRecord> {
+        if !self.can_undo() {
+            return None;
+        }
+        self.cursor -= 1;
+        self.records.get(self.cursor)
+    }
+
+    /// Plays the last undone move again, and returns its record.
+    pub fn redo(&mut self) -> Option<&MoveRecord> {
+        if !self.can_redo() {
+            return None;
+        }
+        self.cursor += 1;
+        self.records.get(self.cursor - 1)
+    }
+}
+
+
+#[cfg(test)]
+mod history_test {
+    use crate::{
+        controller::{game_state::GameState, history::{History, MoveRecord}},
+        game::{
+            assets::{piece::{Piece, UsedPiece}, player::{Player, Players}},
+            rule::{color::Color, moves::Move, position::Position}
+        },
+        tests::helper::board_helper::BoardHelper
+    };
+
+    fn play(history: &mut History, m: Move) {
+        let before = history.current().clone();
+        let after = before.play(m).unwrap();
+        history.push(MoveRecord::new(m, &before, &after), after);
+    }
+
+    #[test]
+    fn when_move_is_recorded_then_flipped_positions_and_pieces_are_kept() {
+        let state = GameState::new(Players::new(Player::Human, Player::Human));
+        let mut history = History::new(state);
+        let piece = Piece::new(Color::Black, 3);
+        play(&mut history, Move::put(Position{x: 4, y: 2}, piece));
+
+        let record = &history.records()[0];
+        assert_eq!(record.color, Color::Black);
+        assert_eq!(record.flipped, vec![Position{x: 4, y: 3}]);
+        assert!(!record.passed);
+        assert!(record.penalties.is_empty());
+        assert_eq!(record.pieces.black.get(&3), Some(&3));
+        assert!(record.used.piece_set.contains(&piece));
+    }
+
+    #[test]
+    fn when_opponent_has_to_pass_then_penalty_is_recorded() {
+        let board = BoardHelper::make_board(vec![(0, 0, 1)], vec![(1, 0, 1), (0, 1, 1)]);
+        let state = GameState {
+            board,
+            used: UsedPiece::new().add_piece(Piece::new(Color::Black, 7)),
+            ..GameState::new(Players::new(Player::Human, Player::Human))
+        };
+        let mut history = History::new(state);
+        play(&mut history, Move::put(Position{x: 2, y: 0}, Piece::new(Color::Black, 1)));
+
+        let record = &history.records()[0];
+        assert_eq!(history.current().turn.color, Color::Black);
+        assert!(record.passed);
+        assert_eq!(record.penalties, vec![Piece::new(Color::Black, 7)]);
+    }
+
+    #[test]
+    fn when_move_is_played_after_undo_then_redo_is_discarded() {
+        let state = GameState::new(Players::new(Player::Human, Player::Human));
+        let mut history = History::new(state.clone());
+        play(&mut history, Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 1)));
+        play(&mut history, Move::put(Position{x: 3, y: 2}, Piece::new(Color::White, 1)));
+
+        assert!(history.undo().is_some());
+        assert!(history.can_redo());
+        play(&mut history, Move::put(Position{x: 5, y: 2}, Piece::new(Color::White, 2)));
+        assert!(!history.can_redo());
+        assert_eq!(history.records().len(), 2);
+
+        assert!(history.undo().is_some());
+        assert!(history.undo().is_some());
+        assert!(history.undo().is_none());
+        assert_eq!(history.current(), &state);
+    }
+}
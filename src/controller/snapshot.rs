@@ -0,0 +1,239 @@
+//! `Snapshot` is the JSON form of a `GameState`.
+//!
+//! The schema is versioned by `SCHEMA_VERSION`, which is raised whenever the JSON changes
+//! in a way older readers cannot follow. Version 2 looks like this:
+//!
+//! ```text
+//! {
+//!   "version": 2,
+//!   "state": {
+//!     "board": { "squares": [["Empty", {"Put": {"color": "Black", "value": 1}}, ...], ...] },
+//!     "pieces": { "black": {"1": 5, "2": 5, ...}, "white": {"1": 5, ...} },
+//!     "used": { "piece_set": [{"color": "Black", "value": 3}, ...] },
+//!     "turn": { "color": "Black", "player": "Human" },
+//!     "players": { "black": "Human", "white": { "Com": { "AlphaBeta": { "depth": 3, "node_budget": null } } } },
+//!     "rules": {
+//!       "board_size": 8,
+//!       "piece_set": { "black": {"1": 5, ...}, "white": {"1": 5, ...} },
+//!       "flip_rule": "StrictSum",
+//!       "takebacks": true
+//!     },
+//!     "move_number": 1
+//!   }
+//! }
+//! ```
+//!
+//! * `board.squares[x][y]` is the square of the column `x` and the row `y`.
+//! * The keys of `pieces` and `piece_set` are the values of the pieces, and the values are their numbers.
+//! * `used.piece_set` is sorted by color, black first, and then by value,
+//!   so the same state is always written as the same JSON.
+//! * A COM player is written with its `StrategyConfig`, such as `{"Com": "Greedy"}`.
+//!
+//! Version 1 wrote a COM player as `"Com"`, before it had a strategy.
+//!
+//! A snapshot is checked after it is read, so a state which cannot be played is rejected
+//! instead of being trusted: the board must be square with one of the `SIZES`, the size of the `rules`,
+//! and every piece on the board, left or used must have a value of the `piece_set` of the `rules`.
+//!
+//! # Examples
+//!
+//! ```
+//! use number_reversi::controller::{game_state::GameState, snapshot::Snapshot};
+//! use number_reversi::game::assets::player::{Player, Players};
+//!
+//! let state = GameState::new(Players::new(Player::Human, Player::RANDOM_COM));
+//! let json = Snapshot::new(state.clone()).to_json();
+//! assert_eq!(Snapshot::from_json(&json).unwrap().state, state);
+//! ```
+
+use std::{error::Error, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::{assets::{board::SIZES, square::Square}, rule::color::Color};
+
+use super::game_state::GameState;
+
+/// The version of the JSON schema written by `Snapshot::to_json`.
+pub const SCHEMA_VERSION: u32 = 2;
+
+/// A `GameState` with the version of the schema it is written in.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub version: u32,
+    pub state: GameState,
+}
+impl Snapshot {
+    /// Creates a `Snapshot` of the `GameState` in the current schema.
+    pub fn new(state: GameState) -> Self {
+        Self { version: SCHEMA_VERSION, state }
+    }
+
+    /// Writes the `Snapshot` as JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("a snapshot is always serializable")
+    }
+
+    /// Reads a `Snapshot` from JSON.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SnapshotError::UnsupportedVersion` if the JSON is written in another version of the schema,
+    /// `SnapshotError::InvalidJson` if it does not follow the schema,
+    /// and `SnapshotError::InvalidState` if the state it describes cannot be played.
+    pub fn from_json(json: &str) -> Result<Self, SnapshotError> {
+        let value = serde_json::from_str::<serde_json::Value>(json)
+            .map_err(|e| SnapshotError::InvalidJson(e.to_string()))?;
+        let version = value.get("version").and_then(serde_json::Value::as_u64)
+            .ok_or_else(|| SnapshotError::InvalidJson("missing field `version`".to_string()))?;
+        if version != SCHEMA_VERSION as u64 {
+            return Err(SnapshotError::UnsupportedVersion(version));
+        }
+        let snapshot = serde_json::from_value::<Self>(value).map_err(|e| SnapshotError::InvalidJson(e.to_string()))?;
+        validate(&snapshot.state).map_err(SnapshotError::InvalidState)?;
+        Ok(snapshot)
+    }
+}
+
+/// Checks that the `GameState` can be played, and returns what is wrong with it otherwise.
+fn validate(state: &GameState) -> Result<(), String> {
+    let size = state.board.size();
+    if !SIZES.contains(&size) || state.board.squares.iter().any(|column| column.len() != size) {
+        return Err("the board is not square with a supported size".to_string());
+    }
+    if state.rules.board_size != size {
+        return Err(format!("the board is {0}x{0} but the rules are for {1}x{1}", size, state.rules.board_size));
+    }
+    // a reversed piece keeps its value, so a piece on the board may have a value of either color
+    let piece_set = &state.rules.piece_set;
+    let known = |value: &u8| piece_set.black.contains_key(value) || piece_set.white.contains_key(value);
+    let mut on_board = state.board.squares.iter().flatten().filter_map(|square| match square {
+        Square::Put(piece) => Some(piece),
+        Square::Empty => None,
+    });
+    if let Some(piece) = on_board.find(|piece| !known(&piece.value)) {
+        return Err(format!("the piece {} on the board is not in the piece set", piece.value));
+    }
+    for color in [Color::Black, Color::White] {
+        let pieces = match color {
+            Color::Black => &state.pieces.black,
+            Color::White => &state.pieces.white,
+        };
+        if let Some(value) = pieces.keys().find(|value| !piece_set.get(color).contains_key(value)) {
+            return Err(format!("the {:?} pieces of the value {} are not in the piece set", color, value));
+        }
+        // a used piece is given back as the penalty of a pass, so it must be one of the pieces left
+        let mut used = state.used.piece_set.iter().filter(|piece| piece.color == color);
+        if let Some(piece) = used.find(|piece| !pieces.contains_key(&piece.value)) {
+            return Err(format!("the used {:?} piece {} is not in the piece set", color, piece.value));
+        }
+    }
+    Ok(())
+}
+
+/// Represents why a `Snapshot` cannot be read.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapshotError {
+    /// The JSON is malformed or does not follow the schema.
+    InvalidJson(String),
+    /// The JSON is written in a version of the schema that is not supported.
+    UnsupportedVersion(u64),
+    /// The JSON follows the schema, but the state it describes cannot be played.
+    InvalidState(String),
+}
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnapshotError::InvalidJson(e) => write!(f, "invalid snapshot: {}", e),
+            SnapshotError::UnsupportedVersion(version) =>
+                write!(f, "snapshot version {} is not supported (expected {})", version, SCHEMA_VERSION),
+            SnapshotError::InvalidState(e) => write!(f, "invalid game state: {}", e),
+        }
+    }
+}
+impl Error for SnapshotError {}
+
+
+#[cfg(test)]
+mod snapshot_test {
+    use crate::{
+        controller::{
+            game_engine::GameEngine, game_state::GameState,
+            snapshot::{Snapshot, SnapshotError}
+        },
+        game::{
+            assets::{board::Board, piece::Piece, player::{Player, Players}, square::Square, strategy_config::StrategyConfig},
+            rule::{color::Color, moves::Move, position::Position, rules::Rules, score::Score, turn::Turn}
+        }
+    };
+
+    #[test]
+    fn when_game_is_played_then_every_state_round_trips() {
+        let rules = Rules { board_size: 6, ..Rules::default() };
+        let mut engine = GameEngine::with_rules(Players::new(Player::Human, Player::RANDOM_COM), rules);
+        while let Some(m) = engine.legal_moves().last().copied() {
+            engine.play(m).unwrap();
+            let json = Snapshot::new(engine.state().clone()).to_json();
+            assert_eq!(&Snapshot::from_json(&json).unwrap().state, engine.state());
+        }
+    }
+
+    #[test]
+    fn when_same_state_is_written_twice_then_json_is_the_same() {
+        let state = GameState::new(Players::new(Player::Human, Player::Human));
+        let state = state.play(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 3))).unwrap();
+        let state = state.play(Move::put(Position{x: 3, y: 2}, Piece::new(Color::White, 8))).unwrap();
+        let json = Snapshot::new(state.clone()).to_json();
+        assert_eq!(Snapshot::new(state.clone()).to_json(), json);
+        assert!(json.starts_with("{\"version\":2,"));
+        assert!(json.contains("\"used\":{\"piece_set\":[{\"color\":\"Black\",\"value\":3},{\"color\":\"White\",\"value\":8}]}"));
+        assert!(json.contains("\"turn\":{\"color\":\"Black\",\"player\":\"Human\"}"));
+    }
+
+    #[test]
+    fn when_types_are_serialized_then_schema_is_stable() {
+        let board = Board::with_size(4);
+        assert_eq!(serde_json::to_string(&board.squares[0][0]).unwrap(), "\"Empty\"");
+        assert_eq!(serde_json::to_string(&board.squares[1][1]).unwrap(), "{\"Put\":{\"color\":\"Black\",\"value\":1}}");
+        assert_eq!(serde_json::to_string(&Position{x: 2, y: 5}).unwrap(), "{\"x\":2,\"y\":5}");
+        assert_eq!(serde_json::to_string(&Score{black: 3, white: 4}).unwrap(), "{\"black\":3,\"white\":4}");
+        let turn = Turn{color: Color::White, player: Player::Com(StrategyConfig::Mcts { iterations: 100 })};
+        let json = "{\"color\":\"White\",\"player\":{\"Com\":{\"Mcts\":{\"iterations\":100}}}}";
+        assert_eq!(serde_json::to_string(&turn).unwrap(), json);
+        assert_eq!(serde_json::from_str::<Turn>(json).unwrap(), turn);
+        assert_eq!(serde_json::from_str::<Square>("\"Empty\"").unwrap(), Square::Empty);
+    }
+
+    #[test]
+    fn when_version_is_unknown_then_returns_error() {
+        let json = Snapshot::new(GameState::new(Players::new(Player::Human, Player::Human))).to_json();
+        let json = json.replacen("\"version\":2", "\"version\":1", 1);
+        assert_eq!(Snapshot::from_json(&json), Err(SnapshotError::UnsupportedVersion(1)));
+        assert!(matches!(Snapshot::from_json("{\"version\":2}"), Err(SnapshotError::InvalidJson(_))));
+        assert!(matches!(Snapshot::from_json("[]"), Err(SnapshotError::InvalidJson(_))));
+    }
+
+    #[test]
+    fn when_state_cannot_be_played_then_returns_error() {
+        let state = GameState::new(Players::new(Player::Human, Player::Human));
+        let invalid = |state: GameState| matches!(
+            Snapshot::from_json(&Snapshot::new(state).to_json()),
+            Err(SnapshotError::InvalidState(_))
+        );
+        let mut missing_square = state.clone();
+        missing_square.board.squares[0].pop();
+        assert!(invalid(missing_square));
+        assert!(invalid(GameState { board: Board::with_size(4), ..state.clone() }));
+        assert!(invalid(GameState { rules: Rules { board_size: 6, ..Rules::default() }, ..state.clone() }));
+        let mut unknown_piece = state.clone();
+        unknown_piece.board.squares[0][0] = Square::Put(Piece::new(Color::Black, 11));
+        assert!(invalid(unknown_piece));
+        let mut unknown_count = state.clone();
+        unknown_count.pieces.white.insert(0, 1);
+        assert!(invalid(unknown_count));
+        let mut unknown_used = state.clone();
+        unknown_used.used.piece_set.insert(Piece::new(Color::White, 12));
+        assert!(invalid(unknown_used));
+        assert!(!invalid(state));
+    }
+}
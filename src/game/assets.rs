@@ -1,5 +1,9 @@
 pub mod board;
+pub mod bit_board;
 pub mod piece;
 pub mod pieces;
+pub mod piece_set;
 pub mod square;
-pub mod player;
\ No newline at end of file
+pub mod player;
+pub mod strategy_config;
+pub mod level;
\ No newline at end of file
@@ -0,0 +1,332 @@
+//! `BitBoard` is a compact representation of the state of the board.
+//!
+//! Each color is represented as a 64 bit mask, where the bit `y * 8 + x` is set when
+//! the color has a piece at `Position{x, y}`, and the values of the pieces are packed in an array.
+//! Since `BitBoard` is `Copy`, search-based AIs can make and unmake moves without any heap allocation:
+//! the COMs search the standard board on a `BitState`, which is a `BitBoard` with the pieces of the players.
+//! Only the standard 8x8 board fits in the masks, so converting a `Board` of another size fails.
+//!
+//! # Examples
+//!
+//! ```
+//! use number_reversi::game::assets::{bit_board::{BitBoard, UnsupportedSize}, board::Board, piece::Piece};
+//! use number_reversi::game::rule::{color::Color, flip_rule::BuiltinFlipRule, position::Position, score::Score};
+//!
+//! let board = BitBoard::try_from(&Board::set_initial_state()).unwrap();
+//! assert_eq!(board.legal_moves(Color::Black).count_ones(), 4);
+//!
+//! let board = board.put(Position{x: 4, y: 2}, Piece::new(Color::Black, 3), &BuiltinFlipRule::StrictSum);
+//! assert_eq!(board.score(), Score { black: 6, white: 1 });
+//! ```
+//!
+//! # Methods
+//!
+//! * `get` - returns the `Piece` at the specified `Position`
+//! * `legal_moves` - returns the mask of the squares where the color can put a piece
+//! * `candidates` - returns the mask of the pieces sandwiched by a piece put at the specified `Position`
+//! * `flips` - returns the mask of the pieces reversed by a piece put at the specified `Position`
+//! * `put` - returns a new `BitBoard` with the piece put and the pieces reversed
+//! * `score` - returns the `Score` of black and white pieces
+
+use std::{error::Error, fmt};
+
+use crate::game::rule::{
+    color::Color,
+    flip_rule::FlipRule,
+    position::Position,
+    score::Score
+};
+
+use super::{board::Board, piece::Piece, square::Square};
+
+const SIZE: usize = 8;
+/// Every square except the ones with `x == 0`.
+const NOT_LEFT_EDGE: u64 = 0xfefe_fefe_fefe_fefe;
+/// Every square except the ones with `x == 7`.
+const NOT_RIGHT_EDGE: u64 = 0x7f7f_7f7f_7f7f_7f7f;
+const DIRECTIONS: [(isize, isize); 8] = [
+    (0, -1), (1, -1), (1, 0), (1, 1), (0, 1), (-1, 1), (-1, 0), (-1, -1)
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BitBoard {
+    pub black: u64,
+    pub white: u64,
+    /// The values of the pieces, indexed by `y * 8 + x`. Empty squares have the value 0.
+    pub values: [u8; SIZE * SIZE],
+}
+impl BitBoard {
+    /// Returns the bit of the `Position`.
+    pub fn bit(position: Position) -> u64 {
+        1 << (position.y * SIZE + position.x)
+    }
+
+    /// Returns the `Position`s of the bits set in the mask, in ascending order of the bit index.
+    pub fn positions(mask: u64) -> Vec<Position> {
+        bits(mask).map(|i| Position{x: i % SIZE, y: i / SIZE}).collect()
+    }
+
+    fn own_and_opponent(&self, color: Color) -> (u64, u64) {
+        match color {
+            Color::Black => (self.black, self.white),
+            Color::White => (self.white, self.black),
+        }
+    }
+
+    /// Returns the mask of the squares that have a piece.
+    pub fn occupied(&self) -> u64 {
+        self.black | self.white
+    }
+
+    /// Returns the `Piece` at the `Position`, or `None` if the square is empty.
+    pub fn get(&self, position: Position) -> Option<Piece> {
+        let bit = Self::bit(position);
+        let value = self.values[position.y * SIZE + position.x];
+        if self.black & bit != 0 {
+            Some(Piece::new(Color::Black, value))
+        } else if self.white & bit != 0 {
+            Some(Piece::new(Color::White, value))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the mask of the empty squares where the color can put a piece,
+    /// that is, the squares from which a line of opponent pieces is closed by a piece of the color.
+    pub fn legal_moves(&self, color: Color) -> u64 {
+        let (own, opponent) = self.own_and_opponent(color);
+        let empty = !(own | opponent);
+        DIRECTIONS.iter().fold(0, |moves, d| {
+            let mut line = shift(own, *d) & opponent;
+            for _ in 0..SIZE - 3 {
+                line |= shift(line, *d) & opponent;
+            }
+            moves | (shift(line, *d) & empty)
+        })
+    }
+
+    /// Returns the mask of the opponent pieces sandwiched by a piece of the color put at the `Position`
+    /// and the index of the piece closing the line, for each direction.
+    /// A direction without such a line has an empty mask.
+    fn lines(&self, position: Position, color: Color) -> [(u64, usize); 8] {
+        let (own, opponent) = self.own_and_opponent(color);
+        let bit = Self::bit(position);
+        DIRECTIONS.map(|d| {
+            let mut line = 0;
+            let mut next = shift(bit, d);
+            while next & opponent != 0 {
+                line |= next;
+                next = shift(next, d);
+            }
+            if next & own != 0 {
+                (line, next.trailing_zeros() as usize)
+            } else {
+                (0, 0)
+            }
+        })
+    }
+
+    /// Returns the mask of the opponent pieces sandwiched by a piece of the color put at the `Position`,
+    /// regardless of their values.
+    pub fn candidates(&self, position: Position, color: Color) -> u64 {
+        self.lines(position, color).iter().fold(0, |mask, (line, _)| mask | line)
+    }
+
+    /// Returns the mask of the pieces reversed by the `Piece` put at the `Position`.
+    /// Which of the sandwiched pieces are reversed is decided by the `FlipRule`.
+    pub fn flips(&self, position: Position, piece: Piece, rule: &impl FlipRule) -> u64 {
+        self.lines(position, piece.color).iter().fold(0, |mask, (line, end)| {
+            let opposite = self.values[*end];
+            bits(*line)
+                .filter(|i| rule.flips(self.values[*i], piece.value, opposite))
+                .fold(mask, |mask, i| mask | (1 << i))
+        })
+    }
+
+    /// Returns a new `BitBoard` with the `Piece` put at the `Position` and the pieces reversed.
+    pub fn put(&self, position: Position, piece: Piece, rule: &impl FlipRule) -> Self {
+        let flips = self.flips(position, piece, rule);
+        let bit = Self::bit(position);
+        let mut board = *self;
+        match piece.color {
+            Color::Black => {
+                board.black |= bit | flips;
+                board.white &= !flips;
+            },
+            Color::White => {
+                board.white |= bit | flips;
+                board.black &= !flips;
+            },
+        }
+        board.values[position.y * SIZE + position.x] = piece.value;
+        board
+    }
+
+    /// Returns the `Score` of the black and white pieces.
+    pub fn score(&self) -> Score {
+        let sum = |mask: u64| bits(mask).map(|i| self.values[i] as usize).sum();
+        Score {
+            black: sum(self.black),
+            white: sum(self.white),
+        }
+    }
+}
+
+/// The error returned when a `Board` is not 8x8.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnsupportedSize(pub usize);
+impl fmt::Display for UnsupportedSize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "a {0}x{0} board cannot be represented as a bit board", self.0)
+    }
+}
+impl Error for UnsupportedSize {}
+
+impl TryFrom<&Board> for BitBoard {
+    type Error = UnsupportedSize;
+
+    fn try_from(board: &Board) -> Result<Self, Self::Error> {
+        if board.size() != SIZE {
+            return Err(UnsupportedSize(board.size()));
+        }
+        let mut bit_board = BitBoard { black: 0, white: 0, values: [0; SIZE * SIZE] };
+        for (x, column) in board.squares.iter().enumerate() {
+            for (y, square) in column.iter().enumerate() {
+                if let Square::Put(piece) = square {
+                    let position = Position{x, y};
+                    match piece.color {
+                        Color::Black => bit_board.black |= BitBoard::bit(position),
+                        Color::White => bit_board.white |= BitBoard::bit(position),
+                    }
+                    bit_board.values[y * SIZE + x] = piece.value;
+                }
+            }
+        }
+        Ok(bit_board)
+    }
+}
+
+impl From<BitBoard> for Board {
+    fn from(bit_board: BitBoard) -> Self {
+        let mut squares = vec![vec![Square::Empty; SIZE]; SIZE];
+        for (x, column) in squares.iter_mut().enumerate() {
+            for (y, square) in column.iter_mut().enumerate() {
+                if let Some(piece) = bit_board.get(Position{x, y}) {
+                    *square = Square::Put(piece);
+                }
+            }
+        }
+        Board{squares}
+    }
+}
+
+/// Returns the indices of the bits set in the mask, in ascending order.
+pub fn bits(mut mask: u64) -> impl Iterator<Item = usize> {
+    std::iter::from_fn(move || {
+        if mask == 0 {
+            return None;
+        }
+        let i = mask.trailing_zeros() as usize;
+        mask &= mask - 1;
+        Some(i)
+    })
+}
+
+/// Shifts every bit of the mask by one square in the direction, dropping the bits moved off the board.
+fn shift(mask: u64, direction: (isize, isize)) -> u64 {
+    let shifted = match direction.1 {
+        -1 => mask >> SIZE,
+        1 => mask << SIZE,
+        _ => mask,
+    };
+    match direction.0 {
+        -1 => (shifted >> 1) & NOT_RIGHT_EDGE,
+        1 => (shifted << 1) & NOT_LEFT_EDGE,
+        _ => shifted,
+    }
+}
+
+
+#[cfg(test)]
+mod bit_board_test {
+    use crate::{
+        controller::game_state::GameState,
+        game::{
+            assets::{bit_board::{BitBoard, UnsupportedSize}, board::Board, piece::Piece, player::{Player, Players}},
+            events::reverse::reverse,
+            rule::{color::Color, flip_rule::BuiltinFlipRule, legal_moves::LegalMoves, moves::Move, position::Position}
+        },
+        tests::helper::board_helper::BoardHelper
+    };
+
+    #[test]
+    fn when_converted_back_then_board_is_the_same() {
+        let board = BoardHelper::make_board(vec![(0, 0, 3), (7, 7, 10)], vec![(7, 0, 2), (0, 7, 9)]);
+        assert_eq!(Board::from(BitBoard::try_from(&board).unwrap()), board);
+    }
+
+    #[test]
+    fn when_board_is_not_8x8_then_it_cannot_be_converted() {
+        assert_eq!(BitBoard::try_from(&Board::with_size(6)), Err(UnsupportedSize(6)));
+        assert_eq!(BitBoard::try_from(&Board::with_size(10)), Err(UnsupportedSize(10)));
+    }
+
+    #[test]
+    fn when_game_starts_then_legal_moves_are_the_4_squares() {
+        let board = BitBoard::try_from(&Board::set_initial_state()).unwrap();
+        assert_eq!(BitBoard::positions(board.legal_moves(Color::Black)), vec![
+            Position{x: 4, y: 2}, Position{x: 5, y: 3}, Position{x: 2, y: 4}, Position{x: 3, y: 5}
+        ]);
+    }
+
+    #[test]
+    fn when_line_reaches_the_edge_then_it_does_not_wrap_around() {
+        let board = BoardHelper::make_board(vec![(0, 1, 1)], vec![(7, 0, 1)]);
+        let board = BitBoard::try_from(&board).unwrap();
+        assert_eq!(board.legal_moves(Color::Black), 0);
+        assert_eq!(board.legal_moves(Color::White), 0);
+    }
+
+    #[test]
+    fn when_sandwiched_piece_is_not_less_than_sum_then_it_is_not_reversed() {
+        let board = BoardHelper::make_board(
+            vec![(0, 0, 2)],
+            vec![(1, 0, 3), (2, 0, 6), (0, 1, 2), (0, 2, 5), (0, 3, 1)]
+        );
+        let board = BitBoard::try_from(&board).unwrap();
+        let piece = Piece::new(Color::Black, 3);
+        assert_eq!(board.candidates(Position{x: 3, y: 0}, Color::Black),
+            BitBoard::bit(Position{x: 1, y: 0}) | BitBoard::bit(Position{x: 2, y: 0}));
+        let rule = BuiltinFlipRule::StrictSum;
+        assert_eq!(board.flips(Position{x: 3, y: 0}, piece, &rule), BitBoard::bit(Position{x: 1, y: 0}));
+        assert_eq!(board.flips(Position{x: 0, y: 4}, piece, &rule),
+            BitBoard::bit(Position{x: 0, y: 3}) | BitBoard::bit(Position{x: 0, y: 1}));
+    }
+
+    #[test]
+    fn when_games_are_played_then_results_match_the_board_api() {
+        let mut state = GameState::new(Players::new(Player::RANDOM_COM, Player::RANDOM_COM));
+        let mut i = 0;
+        while !state.is_over() {
+            let bit_board = BitBoard::try_from(&state.board).unwrap();
+            let legal_moves = LegalMoves::new(&state.board, state.turn);
+            assert_eq!(
+                BitBoard::positions(bit_board.legal_moves(state.turn.color)).len(),
+                legal_moves.positions().len()
+            );
+            assert_eq!(bit_board.score(), state.board.get_score());
+
+            let moves = state.legal_moves();
+            let m = moves[i * 7 % moves.len()];
+            if let Move::Put { position, piece } = m {
+                let candidates = legal_moves.get(position).unwrap().clone();
+                for rule in BuiltinFlipRule::ALL {
+                    let reversed = reverse(state.board.put_piece(position, piece), piece, candidates.clone(), &rule);
+                    assert_eq!(bit_board.put(position, piece, &rule), BitBoard::try_from(&reversed).unwrap());
+                }
+            }
+            state = state.play(m).unwrap();
+            i += 1;
+        }
+    }
+}
@@ -3,12 +3,12 @@
 //! # Examples
 //! 
 //! ```
-//! use number-reversi::game::rule::{
+//! use number_reversi::game::rule::{
 //!     color::Color, 
 //!     position::Position,
 //!     score::Score
 //! };
-//! use number-reversi::game::assets::{board::Board, square::Square, piece::Piece};
+//! use number_reversi::game::assets::{board::Board, square::Square, piece::Piece};
 //! 
 //! let board = Board::set_initial_state();
 //! 
@@ -17,14 +17,41 @@
 //! 
 //! # Methods
 //! 
-//! * `set_initial_state` - returns a new `Board` instance with the initial state of the game
+//! * `set_initial_state` - returns a new 8x8 `Board` instance with the initial state of the game
+//! * `with_size` - returns a new `Board` instance of the specified size with the initial state of the game
+//! * `size` - returns the number of squares on each side of the board
+//! * `contains` - returns a boolean indicating whether the `Position` is on the board
 //! * `is_put` - returns a boolean indicating whether the piece is put at the specified `Position`
 //! * `put_piece` - returns a new `Board` instance with the piece put at the specified `Position`
 //! * `get_squares` - returns a copy of all `Square`s in the board
 //! * `get_score` - returns the `Score` of black and white pieces
-//! * `has_puttable` - returns a boolean indicating whether any `Square` is `Puttable`
+//! * `is_full` - returns a boolean indicating whether every `Square` has a piece
+//! * `diagram` - returns the board as a text diagram with some empty squares marked
+//! * `parse_diagram` - returns the `Board` and the marked positions read from a text diagram
+//!
+//! # Diagrams
+//!
+//! A `Board` is written as a grid with the columns labelled `a`, `b`, ... and the rows labelled from `1`,
+//! the same as the notation of `Position`. An empty square is `.`, a piece is its color and value such as
+//! `b3` or `w10`, and `*` marks an empty square, for example where a piece can be put.
+//! `Board` implements `Display` and `FromStr` with this format, so boards can be written inline in tests.
+//!
+//! ```
+//! use number_reversi::game::assets::{board::Board, piece::Piece, square::Square};
+//! use number_reversi::game::rule::color::Color;
+//!
+//! let board = "
+//!       a   b   c   d
+//!   1   .   .   .   .
+//!   2   .  b1 w10   .
+//!   3   .  w1  b1   .
+//!   4   .   .   .   .
+//! ".parse::<Board>().unwrap();
+//! assert_eq!(board.squares[2][1], Square::Put(Piece::new(Color::White, 10)));
+//! assert_eq!(board.to_string().parse::<Board>(), Ok(board));
+//! ```
 
-use rand::Rng;
+use std::{error::Error, fmt, str::FromStr};
 
 use crate::game::rule::{
     color::Color, 
@@ -32,14 +59,21 @@ use crate::game::rule::{
     score::Score
 };
 
+use serde::{Deserialize, Serialize};
+
 use super::{square::Square, piece::Piece};
 
-#[derive(Debug, Clone, PartialEq)]
+/// The size of the standard board.
+pub const DEFAULT_SIZE: usize = 8;
+/// The board sizes that can be chosen for a game.
+pub const SIZES: [usize; 4] = [6, 8, 10, 12];
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Board {
     pub squares: Vec<Vec<Square>>,
 }
 impl Board {
-    /// Creates a new `Board` with the initial state of the game.
+    /// Creates a new 8x8 `Board` with the initial state of the game.
     /// The initial state is defined as follows:
     /// - The black pieces are placed in the center of the board, in positions (3, 3) and (4, 4)
     /// - The white pieces are placed in the center of the board, in positions (3, 4) and (4, 3)
@@ -47,22 +81,66 @@ impl Board {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::board::Board;
+    /// use number_reversi::game::assets::board::Board;
     ///
     /// let board = Board::set_initial_state();
     /// ```
     pub fn set_initial_state() -> Self {
-        
+        Self::with_size(DEFAULT_SIZE)
+    }
+
+    /// Creates a new `Board` with `size` squares on each side and the initial state of the game.
+    /// The four initial pieces are placed in the center of the board in the same pattern as on the 8x8 board.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `size` is odd or less than 4, since the initial pieces cannot be centred.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::{board::Board, square::Square, piece::Piece};
+    /// use number_reversi::game::rule::{color::Color, position::Position};
+    ///
+    /// let board = Board::with_size(6);
+    /// assert_eq!(board.size(), 6);
+    /// assert!(board.is_put(Position{x: 2, y: 2}));
+    /// assert_eq!(board.squares[3][3], Square::Put(Piece::new(Color::Black, 1)));
+    /// ```
+    pub fn with_size(size: usize) -> Self {
+        assert!(size >= 4 && size.is_multiple_of(2), "the board size must be an even number of 4 or more: {}", size);
         let mut board = Self {
-            squares: vec![vec![Square::Empty; 8]; 8]
+            squares: vec![vec![Square::Empty; size]; size]
         };
-        board.squares[3][3] = Square::Put(Piece::new(Color::Black, 1));
-        board.squares[4][4] = Square::Put(Piece::new(Color::Black, 1));
-        board.squares[3][4] = Square::Put(Piece::new(Color::White, 1));
-        board.squares[4][3] = Square::Put(Piece::new(Color::White, 1));
+        let center = size / 2;
+        board.squares[center - 1][center - 1] = Square::Put(Piece::new(Color::Black, 1));
+        board.squares[center][center] = Square::Put(Piece::new(Color::Black, 1));
+        board.squares[center - 1][center] = Square::Put(Piece::new(Color::White, 1));
+        board.squares[center][center - 1] = Square::Put(Piece::new(Color::White, 1));
         board
     }
 
+    /// Returns the number of squares on each side of the board.
+    pub fn size(&self) -> usize {
+        self.squares.len()
+    }
+
+    /// Returns a boolean indicating whether the `Position` is on the board.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::board::Board;
+    /// use number_reversi::game::rule::position::Position;
+    ///
+    /// let board = Board::with_size(6);
+    /// assert!(board.contains(Position{x: 5, y: 5}));
+    /// assert!(!board.contains(Position{x: 6, y: 0}));
+    /// ```
+    pub fn contains(&self, position: Position) -> bool {
+        self.squares.get(position.x).and_then(|column| column.get(position.y)).is_some()
+    }
+
     /// Returns a boolean indicating whether a `Piece` has been put in a certain `Position`.
     ///
     /// # Arguments
@@ -72,8 +150,8 @@ impl Board {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::board::Board;
-    /// use number-reversi::game::rule::position::Position;
+    /// use number_reversi::game::assets::board::Board;
+    /// use number_reversi::game::rule::position::Position;
     ///
     /// let board = Board::set_initial_state();
     /// let position = Position{x: 3, y: 3};
@@ -81,10 +159,7 @@ impl Board {
     /// assert!(board.is_put(position));
     /// ```
     pub fn is_put(&self, position: Position) -> bool {
-        match self.squares[position.x][position.y] {
-            Square::Put(_) => true,
-            _ => false,
-        }
+        matches!(self.squares[position.x][position.y], Square::Put(_))
     }
 
     /// Returns a new `Board` after putting a `Piece` in a certain `Position`.
@@ -97,9 +172,9 @@ impl Board {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::{board::Board, piece::Piece};
-    /// use number-reversi::game::rule::position::Position;
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::{board::Board, piece::Piece};
+    /// use number_reversi::game::rule::position::Position;
+    /// use number_reversi::game::rule::color::Color;
     ///
     /// let board = Board::set_initial_state();
     /// let position = Position{x: 0, y: 0};
@@ -121,7 +196,7 @@ impl Board {
     /// # Example
     ///
     /// ```
-    /// use number-reversi::game::assets::{board::Board, square::Square};
+    /// use number_reversi::game::assets::{board::Board, square::Square};
     ///
     /// let board = Board::set_initial_state();
     /// let squares = board.get_squares();
@@ -131,13 +206,13 @@ impl Board {
         self.squares.clone()
     }
 
-    fn calculate_score(&self, color: Color) -> u8 {
+    fn calculate_score(&self, color: Color) -> usize {
         self.squares
         .iter()
         .flatten()
         .filter_map(|x| {
             match x {
-                Square::Put(p) if p.color == color => Some(p.value),
+                Square::Put(p) if p.color == color => Some(p.value as usize),
                 _ => None,
             }
         })
@@ -149,8 +224,8 @@ impl Board {
     /// # Example
     ///
     /// ```
-    /// use number-reversi::game::assets::board::Board;
-    /// use number-reversi::game::rule::score::Score;
+    /// use number_reversi::game::assets::board::Board;
+    /// use number_reversi::game::rule::score::Score;
     ///
     /// let board = Board::set_initial_state();
     /// let score = board.get_score();
@@ -161,42 +236,194 @@ impl Board {
         let white_score = self.calculate_score(Color::White);
 
         Score {
-            black: black_score as usize,
-            white: white_score as usize,
+            black: black_score,
+            white: white_score,
         }
     }
 
-    /// Check if there is any puttable square on the board.
+    /// Check if every square on the board has a piece.
     ///
     /// # Example
     ///
     /// ```
-    /// use number-reversi::game::assets::board::Board;
+    /// use number_reversi::game::assets::board::Board;
     ///
     /// let board = Board::set_initial_state();
-    /// assert_eq!(board.has_puttable(), false);
+    /// assert!(!board.is_full());
     /// ```
-    pub fn has_puttable(&self) -> bool {
-        self.squares.iter().flatten().any(|x| {
-            match x {
-                Square::Puttable(_) => true,
-                _ => false,
+    pub fn is_full(&self) -> bool {
+        self.squares.iter().flatten().all(|x| matches!(x, Square::Put(_)))
+    }
+
+    /// Returns the board as a text diagram, with the empty squares of `marked` written as `*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::board::Board;
+    /// use number_reversi::game::rule::position::Position;
+    ///
+    /// let board = Board::with_size(4);
+    /// let diagram = board.diagram(&[Position{x: 1, y: 0}, Position{x: 1, y: 1}]);
+    /// assert_eq!(diagram, [
+    ///     "      a   b   c   d",
+    ///     "  1   .   *   .   .",
+    ///     "  2   .  b1  w1   .",
+    ///     "  3   .  w1  b1   .",
+    ///     "  4   .   .   .   .",
+    /// ].join("\n"));
+    /// ```
+    pub fn diagram(&self, marked: &[Position]) -> String {
+        let size = self.size();
+        let mut lines = vec![];
+        let header = (0..size).map(|x| format!("{:>4}", column_label(x))).collect::<String>();
+        lines.push(format!("{:>3}{}", "", header));
+        for y in 0..size {
+            let row = (0..size).map(|x| {
+                let square = match self.squares[x][y] {
+                    Square::Put(piece) => format!("{}{}", color_label(piece.color), piece.value),
+                    Square::Empty if marked.contains(&Position{x, y}) => "*".to_string(),
+                    Square::Empty => ".".to_string(),
+                };
+                format!("{:>4}", square)
+            })
+            .collect::<String>();
+            lines.push(format!("{:>3}{}", y + 1, row));
+        }
+        lines.join("\n")
+    }
+
+    /// Reads a text diagram, and returns the `Board` and the positions marked with `*` row by row.
+    ///
+    /// The column and row labels may be left out. Letters may be written in either case.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `ParseBoardError` if the diagram is not a square grid of `.`, `*` and pieces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::board::Board;
+    /// use number_reversi::game::rule::position::Position;
+    ///
+    /// let (board, marked) = Board::parse_diagram("
+    ///     .  .  *  .
+    ///     .  b1 w1 .
+    ///     .  w1 b1 .
+    ///     .  .  .  .
+    /// ").unwrap();
+    /// assert_eq!(board, Board::with_size(4));
+    /// assert_eq!(marked, vec![Position{x: 2, y: 0}]);
+    /// ```
+    pub fn parse_diagram(s: &str) -> Result<(Self, Vec<Position>), ParseBoardError> {
+        let mut rows = s.lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .filter(|row| !row.is_empty())
+            .collect::<Vec<_>>();
+        let is_header = |row: &Vec<&str>| row.iter().enumerate().all(|(x, label)| label.eq_ignore_ascii_case(&column_label(x)));
+        if rows.first().is_some_and(is_header) {
+            rows.remove(0);
+        }
+        let size = rows.len();
+        if size == 0 {
+            return Err(ParseBoardError::Empty);
+        }
+
+        let mut board = Self { squares: vec![vec![Square::Empty; size]; size] };
+        let mut marked = vec![];
+        for (y, row) in rows.iter().enumerate() {
+            let cells = match row.split_first() {
+                Some((label, cells)) if cells.len() == size && label.parse::<usize>().is_ok() => {
+                    if label.parse::<usize>() != Ok(y + 1) {
+                        return Err(ParseBoardError::WrongRowLabel { row: y + 1, label: label.to_string() });
+                    }
+                    cells
+                },
+                _ => &row[..],
+            };
+            if cells.len() != size {
+                return Err(ParseBoardError::WrongRowLength { row: y + 1, expected: size, actual: cells.len() });
             }
-        })
+            for (x, cell) in cells.iter().enumerate() {
+                let position = Position{x, y};
+                board.squares[x][y] = match *cell {
+                    "." => Square::Empty,
+                    "*" => {
+                        marked.push(position);
+                        Square::Empty
+                    },
+                    _ => Square::Put(parse_piece(cell).ok_or_else(|| ParseBoardError::InvalidSquare {
+                        position,
+                        text: cell.to_string()
+                    })?),
+                };
+            }
+        }
+        Ok((board, marked))
+    }
+}
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.diagram(&[]))
     }
+}
+impl FromStr for Board {
+    type Err = ParseBoardError;
 
-    pub fn get_puttable_position(&self) -> Position {
-        let pos: Vec<Position> = (0..8).flat_map(|i| 
-            (0..8).map(move |j| Position{x: i, y: j})
-        ).filter(|p| match self.squares[p.x][p.y] {
-            Square::Puttable(_) => true,
-            _ => false,
-        }).collect();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_diagram(s).map(|(board, _)| board)
+    }
+}
 
-        let i = rand::thread_rng().gen_range(0..pos.len());
-        pos[i]
+/// Represents why a text diagram cannot be read as a `Board`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseBoardError {
+    /// The diagram has no rows.
+    Empty,
+    /// The row does not have as many squares as the board has rows.
+    WrongRowLength { row: usize, expected: usize, actual: usize },
+    /// The label of the row is not its number.
+    WrongRowLabel { row: usize, label: String },
+    /// The square is neither `.`, `*` nor a piece.
+    InvalidSquare { position: Position, text: String },
+}
+impl fmt::Display for ParseBoardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseBoardError::Empty => write!(f, "the board has no rows"),
+            ParseBoardError::WrongRowLength { row, expected, actual } =>
+                write!(f, "row {} has {} squares, expected {}", row, actual, expected),
+            ParseBoardError::WrongRowLabel { row, label } => write!(f, "row {} is labelled {}", row, label),
+            ParseBoardError::InvalidSquare { position, text } => write!(f, "invalid square at {}: {}", position, text),
+        }
     }
 }
+impl Error for ParseBoardError {}
+
+fn column_label(x: usize) -> String {
+    match u8::try_from(x).ok().filter(|x| *x < 26) {
+        Some(x) => ((b'a' + x) as char).to_string(),
+        None => x.to_string(),
+    }
+}
+
+fn color_label(color: Color) -> char {
+    match color {
+        Color::Black => 'b',
+        Color::White => 'w',
+    }
+}
+
+fn parse_piece(s: &str) -> Option<Piece> {
+    let color = match s.chars().next()?.to_ascii_lowercase() {
+        'b' => Color::Black,
+        'w' => Color::White,
+        _ => return None,
+    };
+    let value = s[1..].parse::<u8>().ok().filter(|value| *value > 0)?;
+    Some(Piece::new(color, value))
+}
 
 
 #[cfg(test)]
@@ -213,6 +440,27 @@ mod tests {
         assert_eq!(board.squares[4][3], Square::Put(Piece::new(Color::White, 1)));
     }
 
+    #[test]
+    fn when_size_is_given_then_initial_pieces_are_centred() {
+        for size in SIZES {
+            let board = Board::with_size(size);
+            let center = size / 2;
+            assert_eq!(board.size(), size);
+            assert_eq!(board.squares.iter().filter(|column| column.len() == size).count(), size);
+            assert_eq!(board.squares[center - 1][center - 1], Square::Put(Piece::new(Color::Black, 1)));
+            assert_eq!(board.squares[center][center], Square::Put(Piece::new(Color::Black, 1)));
+            assert_eq!(board.squares[center - 1][center], Square::Put(Piece::new(Color::White, 1)));
+            assert_eq!(board.squares[center][center - 1], Square::Put(Piece::new(Color::White, 1)));
+            assert_eq!(board.get_score(), Score { black: 2, white: 2 });
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn when_size_is_odd_then_panics() {
+        Board::with_size(7);
+    }
+
     #[test]
     fn is_put_test() {
         let board = Board::set_initial_state();
@@ -221,7 +469,7 @@ mod tests {
         assert!(board.is_put(Position {x: 4, y: 4}));
         assert!(board.is_put(Position {x: 3, y: 4}));
         assert!(board.is_put(Position {x: 4, y: 3}));
-        assert_eq!(board.is_put(Position {x: 0, y: 0}), false);
+        assert!(!board.is_put(Position {x: 0, y: 0}));
     }
 
     #[test]
@@ -229,7 +477,7 @@ mod tests {
         let board = Board::set_initial_state();
         let piece = Piece::new(Color::Black, 1);
         
-        let new_board = board.put_piece(Position {x: 0, y: 0}, piece.clone());
+        let new_board = board.put_piece(Position {x: 0, y: 0}, piece);
         assert_eq!(new_board.squares[0][0], Square::Put(piece));
     }
 
@@ -250,8 +498,40 @@ mod tests {
     }
 
     #[test]
-    fn has_puttable_test() {
-        let board = Board::set_initial_state();
-        assert_eq!(board.has_puttable(), false);
+    fn is_full_test() {
+        let board = Board {
+            squares: vec![vec![Square::Put(Piece::new(Color::White, 10)); 8]; 8]
+        };
+        assert!(board.is_full());
+        assert_eq!(board.get_score(), Score { black: 0, white: 640 });
+        assert!(!Board::set_initial_state().is_full());
+    }
+
+    #[test]
+    fn when_board_is_printed_then_it_is_parsed_back() {
+        for size in SIZES {
+            let board = Board::with_size(size)
+                .put_piece(Position {x: 0, y: 0}, Piece::new(Color::White, 10))
+                .put_piece(Position {x: size - 1, y: 1}, Piece::new(Color::Black, 7));
+            assert_eq!(board.to_string().parse::<Board>(), Ok(board));
+        }
+    }
+
+    #[test]
+    fn when_diagram_has_marks_then_they_are_returned_as_empty_squares() {
+        let board = Board::with_size(4);
+        let marked = vec![Position {x: 2, y: 0}, Position {x: 0, y: 3}];
+        assert_eq!(Board::parse_diagram(&board.diagram(&marked)), Ok((board, marked)));
+    }
+
+    #[test]
+    fn when_diagram_is_malformed_then_returns_error() {
+        assert_eq!("".parse::<Board>(), Err(ParseBoardError::Empty));
+        assert_eq!(". .\n. . .".parse::<Board>(), Err(ParseBoardError::WrongRowLength { row: 2, expected: 2, actual: 3 }));
+        assert_eq!("1 . .\n3 . .".parse::<Board>(), Err(ParseBoardError::WrongRowLabel { row: 2, label: "3".to_string() }));
+        assert_eq!(". x3\n. .".parse::<Board>(), Err(ParseBoardError::InvalidSquare {
+            position: Position {x: 1, y: 0},
+            text: "x3".to_string()
+        }));
     }
 }
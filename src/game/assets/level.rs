@@ -0,0 +1,64 @@
+use super::strategy_config::StrategyConfig;
+
+/// The difficulty levels of COM which can be chosen in the menu.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::assets::{level::Level, strategy_config::StrategyConfig};
+///
+/// assert_eq!(Level::Easy.config(), StrategyConfig::Greedy);
+/// assert_eq!(Level::from_config(Level::Hard.config()), Some(Level::Hard));
+/// assert_eq!(Level::from_config(StrategyConfig::Mcts { iterations: 1 }), None);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Level {
+    /// Puts a random piece on a random square.
+    Random,
+    /// Takes the most points at once.
+    Easy,
+    /// Looks two moves ahead.
+    #[default]
+    Normal,
+    /// Looks three moves ahead.
+    Hard,
+    /// Plays many games to the end in its head.
+    Expert,
+}
+impl Level {
+    /// Every level, from the weakest to the strongest.
+    pub const ALL: [Self; 5] = [
+        Self::Random,
+        Self::Easy,
+        Self::Normal,
+        Self::Hard,
+        Self::Expert,
+    ];
+
+    /// Returns the name of the level shown in the menu and in the game record.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Random => "Random",
+            Self::Easy => "Easy",
+            Self::Normal => "Normal",
+            Self::Hard => "Hard",
+            Self::Expert => "Expert",
+        }
+    }
+
+    /// Returns the `StrategyConfig` of the level, with the budget which keeps its thinking time short.
+    pub fn config(&self) -> StrategyConfig {
+        match self {
+            Self::Random => StrategyConfig::Random,
+            Self::Easy => StrategyConfig::Greedy,
+            Self::Normal => StrategyConfig::AlphaBeta { depth: 2, node_budget: Some(5_000) },
+            Self::Hard => StrategyConfig::AlphaBeta { depth: 3, node_budget: Some(50_000) },
+            Self::Expert => StrategyConfig::Mcts { iterations: 1_000 },
+        }
+    }
+
+    /// Returns the level of the `StrategyConfig`, or `None` if it is not the config of any level.
+    pub fn from_config(config: StrategyConfig) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| level.config() == config)
+    }
+}
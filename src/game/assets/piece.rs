@@ -1,10 +1,12 @@
 use std::collections::HashSet;
 
+use serde::{Deserialize, Serialize, Serializer};
+
 use crate::game::rule::{color::Color, turn::Turn};
 
 /// Represents a Piece on the game board.
 /// A Piece has a color (Black or White) and a value(1-10).
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub struct Piece {
     pub color: Color,
     pub value: u8,
@@ -20,8 +22,8 @@ impl Piece {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::piece::Piece;
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::piece::Piece;
+    /// use number_reversi::game::rule::color::Color;
     /// 
     /// let black_piece = Piece::new(Color::Black, 1);
     /// assert_eq!(black_piece.color, Color::Black);
@@ -42,8 +44,8 @@ impl Piece {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::piece::Piece;
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::piece::Piece;
+    /// use number_reversi::game::rule::color::Color;
     /// 
     /// let black = Piece::new(Color::Black, 1);
     /// assert_eq!(black.reverse(), Piece::new(Color::White, 1));
@@ -61,8 +63,14 @@ impl Piece {
 
 
 /// Represents the set of `Piece` instances that have been used in the game.
-#[derive(Debug, PartialEq, Eq, Clone)]
+///
+/// It records which values each color has used, not how many pieces of each value:
+/// a value used twice is in the set once, so the penalties of the passes give back
+/// at most one piece of each value until the value is used again.
+#[derive(Debug, PartialEq, Eq, Clone, Default, Serialize, Deserialize)]
 pub struct UsedPiece{
+    /// serialized as a list sorted by color and value, so the JSON is stable
+    #[serde(serialize_with = "serialize_sorted")]
     pub piece_set: HashSet<Piece>
 }
 
@@ -72,7 +80,7 @@ impl UsedPiece {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::piece::UsedPiece;
+    /// use number_reversi::game::assets::piece::UsedPiece;
     /// 
     /// let used_piece = UsedPiece::new();
     /// assert!(used_piece.piece_set.is_empty());
@@ -86,8 +94,8 @@ impl UsedPiece {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::piece::{UsedPiece, Piece};
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::piece::{UsedPiece, Piece};
+    /// use number_reversi::game::rule::color::Color;
     /// 
     /// let piece = Piece::new(Color::Black, 1);
     /// let mut used_piece = UsedPiece::new();
@@ -105,8 +113,8 @@ impl UsedPiece {
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::piece::{UsedPiece, Piece};
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::piece::{UsedPiece, Piece};
+    /// use number_reversi::game::rule::color::Color;
     /// 
     /// let piece = Piece::new(Color::Black, 1);
     /// let mut used_piece = UsedPiece::new().add_piece(piece);
@@ -121,26 +129,38 @@ impl UsedPiece {
 
     /// Gets the piece that the opponent will receive when passing.
     /// The piece selected is the highest-valued piece of the opponent color in the `piece_set`.
+    /// Returns `None` if the opponent has not used any piece.
+    /// The piece is removed from the set when it is given back, so the next penalty is the next value used.
     ///
     /// # Examples
     ///
     /// ```
-    /// use number-reversi::game::assets::piece::{UsedPiece, Piece};
-    /// use number-reversi::game::rule::{color::Color, turn::Turn};
+    /// use number_reversi::game::assets::piece::{UsedPiece, Piece};
+    /// use number_reversi::game::assets::player::Player;
+    /// use number_reversi::game::rule::{color::Color, turn::Turn};
     /// 
     /// let mut used_piece = UsedPiece::new();
     /// used_piece = used_piece.add_piece(Piece::new(Color::Black, 1));
     /// used_piece = used_piece.add_piece(Piece::new(Color::Black, 3));
     /// 
-    /// let turn = Turn{color: Color::Black};
-    /// assert_eq!(used_piece.get_add_piece(turn), Piece::new(Color::Black, 3));
+    /// let turn = Turn{color: Color::Black, player: Player::Human};
+    /// assert_eq!(used_piece.get_add_piece(turn), Some(Piece::new(Color::Black, 3)));
+    /// 
+    /// let turn = Turn{color: Color::White, player: Player::Human};
+    /// assert_eq!(used_piece.get_add_piece(turn), None);
     /// ```
-    pub fn get_add_piece(&self, turn: Turn) -> Piece {
+    pub fn get_add_piece(&self, turn: Turn) -> Option<Piece> {
         self.piece_set.iter().filter(|p| p.color == turn.color)
-        .max_by_key(|p| p.value).unwrap().clone()
+        .max_by_key(|p| p.value).copied()
     }
 }
 
+fn serialize_sorted<S: Serializer>(piece_set: &HashSet<Piece>, serializer: S) -> Result<S::Ok, S::Error> {
+    let mut pieces = piece_set.iter().collect::<Vec<_>>();
+    pieces.sort_by_key(|piece| (piece.color == Color::White, piece.value));
+    serializer.collect_seq(pieces)
+}
+
 
 #[cfg(test)]
 mod piece_test {
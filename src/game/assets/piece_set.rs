@@ -0,0 +1,150 @@
+use std::{collections::BTreeMap, ops::RangeInclusive};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::rule::color::Color;
+
+/// PieceSet represents the pieces each player has at the start of a game.
+///
+/// The two colors may have different pieces, so a handicap can be given to either player.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::assets::piece_set::PieceSet;
+/// use number_reversi::game::rule::color::Color;
+///
+/// let piece_set = PieceSet::uniform(1..=5, 6);
+/// assert_eq!(piece_set.black.get(&5), Some(&6));
+/// assert_eq!(piece_set.value_range(Color::White), Some(1..=5));
+/// assert_eq!(piece_set.total(Color::White), 30);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PieceSet {
+    /// the number of black pieces, represented as a map
+    /// from piece value to the number of pieces with that value
+    pub black: BTreeMap<u8, u8>,
+    /// the number of white pieces, represented as a map
+    /// from piece value to the number of pieces with that value
+    pub white: BTreeMap<u8, u8>,
+}
+impl PieceSet {
+    /// Creates a `PieceSet` in which black and white have the same pieces.
+    pub fn symmetric(counts: BTreeMap<u8, u8>) -> Self {
+        Self { black: counts.clone(), white: counts }
+    }
+
+    /// Creates a `PieceSet` in which each color has `count` pieces of every value in `values`.
+    pub fn uniform(values: RangeInclusive<u8>, count: u8) -> Self {
+        Self::symmetric(values.map(|v| (v, count)).collect())
+    }
+
+    /// Creates the standard `PieceSet`: 5, 5, 4, 4, 3, 3, 2, 2, 1 and 1 pieces of the values 1 to 10.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::piece_set::PieceSet;
+    /// use number_reversi::game::rule::color::Color;
+    ///
+    /// let piece_set = PieceSet::standard();
+    /// assert_eq!(piece_set.black.get(&1), Some(&5));
+    /// assert_eq!(piece_set.white.get(&10), Some(&1));
+    /// assert_eq!(piece_set, PieceSet::default());
+    /// ```
+    pub fn standard() -> Self {
+        Self::symmetric((1..=10).map(|i| {
+            let piece_num = match i {
+                1 | 2 => 5,
+                3 | 4 => 4,
+                5 | 6 => 3,
+                7 | 8 => 2,
+                _ => 1
+            };
+            (i, piece_num)
+        }).collect())
+    }
+
+    /// Returns the `PieceSet`s that can be chosen for a new game, with their names.
+    pub fn presets() -> Vec<(&'static str, Self)> {
+        let mut handicap = Self::standard();
+        *handicap.white.entry(9).or_default() += 1;
+        *handicap.white.entry(10).or_default() += 1;
+        vec![
+            ("Standard", Self::standard()),
+            ("Uniform 1-10", Self::uniform(1..=10, 3)),
+            ("Small 1-5", Self::uniform(1..=5, 6)),
+            ("White handicap", handicap),
+        ]
+    }
+
+    /// Returns the pieces of the color.
+    pub fn get(&self, color: Color) -> &BTreeMap<u8, u8> {
+        match color {
+            Color::Black => &self.black,
+            Color::White => &self.white,
+        }
+    }
+
+    /// Returns the range from the smallest to the largest value of the color,
+    /// or `None` if the color has no pieces.
+    pub fn value_range(&self, color: Color) -> Option<RangeInclusive<u8>> {
+        let values = self.get(color);
+        let min = *values.keys().next()?;
+        let max = *values.keys().next_back()?;
+        Some(min..=max)
+    }
+
+    /// Returns the total number of pieces of the color.
+    pub fn total(&self, color: Color) -> usize {
+        self.get(color).values().map(|n| *n as usize).sum()
+    }
+
+    /// Returns whether black and white have the same pieces.
+    pub fn is_symmetric(&self) -> bool {
+        self.black == self.white
+    }
+}
+impl Default for PieceSet {
+    fn default() -> Self {
+        Self::standard()
+    }
+}
+
+
+#[cfg(test)]
+mod piece_set_test {
+    use crate::game::{assets::piece_set::PieceSet, rule::color::Color};
+
+    #[test]
+    fn when_standard_set_then_each_color_has_30_pieces() {
+        let piece_set = PieceSet::standard();
+        assert!(piece_set.is_symmetric());
+        assert_eq!(piece_set.total(Color::Black), 30);
+        assert_eq!(piece_set.total(Color::White), 30);
+        assert_eq!(piece_set.value_range(Color::Black), Some(1..=10));
+    }
+
+    #[test]
+    fn when_handicap_is_given_then_set_is_asymmetric() {
+        let (_, handicap) = PieceSet::presets().into_iter()
+            .find(|(name, _)| *name == "White handicap")
+            .unwrap();
+        assert!(!handicap.is_symmetric());
+        assert_eq!(handicap.total(Color::Black), 30);
+        assert_eq!(handicap.total(Color::White), 32);
+    }
+
+    #[test]
+    fn when_color_has_no_pieces_then_value_range_is_none() {
+        let piece_set = PieceSet { white: Default::default(), ..PieceSet::standard() };
+        assert_eq!(piece_set.value_range(Color::White), None);
+    }
+
+    #[test]
+    fn when_serialized_then_it_is_deserialized_to_the_same_set() {
+        let piece_set = PieceSet::presets().pop().unwrap().1;
+        let json = serde_json::to_string(&piece_set).unwrap();
+        assert_eq!(serde_json::from_str::<PieceSet>(&json).unwrap(), piece_set);
+    }
+}
@@ -1,27 +1,31 @@
 use rand::Rng;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 
-use crate::game::rule::{color::Color, turn::Turn};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::piece::Piece;
+use crate::game::rule::{color::Color, moves::MoveError, turn::Turn};
+
+use super::{piece::Piece, piece_set::PieceSet};
 
 /// Pieces represents the remaining pieces of a player.
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct Pieces {
     /// the number of remaining black pieces, represented as a HashMap 
     /// from piece value to the number of pieces with that value
+    #[serde(serialize_with = "serialize_sorted")]
     pub black: HashMap<u8, u8>,
     /// the number of remaining white pieces, represented as a HashMap 
     /// from piece value to the number of pieces with that value
+    #[serde(serialize_with = "serialize_sorted")]
     pub white: HashMap<u8, u8>,
 }
 impl Pieces {
-    /// make_pieces creates an initial set of pieces for both black and white.
+    /// make_pieces creates the standard initial set of pieces for both black and white.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use number-reversi::game::assets::pieces::Pieces;
+    /// use number_reversi::game::assets::pieces::Pieces;
     /// 
     /// let pieces = Pieces::make_pieces();
     /// assert_eq!(pieces.black.get(&1), Some(&5));
@@ -30,50 +34,61 @@ impl Pieces {
     /// assert_eq!(pieces.white.get(&10), Some(&1));
     /// ```
     pub fn make_pieces() -> Self {
-        let piece_set = (1..=10).map(|i| {
-            let piece_num = match i {
-                1 | 2 => 5,
-                3 | 4 => 4,
-                5 | 6 => 3,
-                7 | 8 => 2,
-                _ => 1
-            };
-            (i, piece_num)
-        }).collect::<HashMap<u8, u8>>();
-        let other_hand = piece_set.clone();
+        Self::from_set(&PieceSet::standard())
+    }
+    /// from_set creates an initial set of pieces from the `PieceSet`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use number_reversi::game::assets::{pieces::Pieces, piece_set::PieceSet};
+    /// 
+    /// let mut piece_set = PieceSet::uniform(1..=5, 6);
+    /// piece_set.white.insert(10, 1);
+    /// let pieces = Pieces::from_set(&piece_set);
+    /// assert_eq!(pieces.black.get(&5), Some(&6));
+    /// assert_eq!(pieces.black.get(&10), None);
+    /// assert_eq!(pieces.white.get(&10), Some(&1));
+    /// ```
+    pub fn from_set(piece_set: &PieceSet) -> Self {
         Self { 
-            black: piece_set,
-            white: other_hand,
+            black: piece_set.black.iter().map(|(v, n)| (*v, *n)).collect::<HashMap<u8, u8>>(),
+            white: piece_set.white.iter().map(|(v, n)| (*v, *n)).collect::<HashMap<u8, u8>>(),
         }
     }
     /// remove a piece, and return a new Pieces instance.
     /// 
+    /// Returns `MoveError::NoPiecesOfValue` if no piece of the value is left.
+    /// 
     /// # Examples
     /// 
     /// ```
-    /// use number-reversi::game::assets::piece::Piece;
-    /// use number-reversi::game::assets::pieces::Pieces;
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::piece::Piece;
+    /// use number_reversi::game::assets::pieces::Pieces;
+    /// use number_reversi::game::rule::{color::Color, moves::MoveError};
     /// 
     /// // remove a black piece1
     /// let pieces = Pieces::make_pieces();
-    /// let pieces = pieces.remove(&Piece::new(Color::Black, 1));
+    /// let pieces = pieces.remove(&Piece::new(Color::Black, 1)).unwrap();
     /// assert_eq!(pieces.black.get(&1), Some(&4));
+    /// 
+    /// // there is only one black piece10
+    /// let piece = Piece::new(Color::Black, 10);
+    /// let pieces = pieces.remove(&piece).unwrap();
+    /// assert_eq!(pieces.remove(&piece), Err(MoveError::NoPiecesOfValue(piece)));
     /// ```
-    pub fn remove(&self, piece: &Piece) -> Self {
-        match piece.color {
-            Color::Black => {
-                let mut piece_set = self.black.clone();
-                let piece_num = piece_set.get_mut(&piece.value).unwrap();
+    pub fn remove(&self, piece: &Piece) -> Result<Self, MoveError> {
+        let mut pieces = self.clone();
+        let piece_set = match piece.color {
+            Color::Black => &mut pieces.black,
+            Color::White => &mut pieces.white,
+        };
+        match piece_set.get_mut(&piece.value) {
+            Some(piece_num) if *piece_num > 0 => {
                 *piece_num -= 1;
-                Self { black: piece_set, white: self.white.clone() }
-            },
-            Color::White => {
-                let mut piece_set = self.white.clone();
-                let piece_num = piece_set.get_mut(&piece.value).unwrap();
-                *piece_num -= 1;
-                Self { black: self.black.clone(), white: piece_set }
+                Ok(pieces)
             },
+            _ => Err(MoveError::NoPiecesOfValue(*piece)),
         }
     }
     /// add a piece, and return a new Pieces instance.
@@ -81,9 +96,9 @@ impl Pieces {
     /// # Examples
     /// 
     /// ```
-    /// use number-reversi::game::assets::piece::Piece;
-    /// use number-reversi::game::assets::pieces::Pieces;
-    /// use number-reversi::game::rule::color::Color;
+    /// use number_reversi::game::assets::piece::Piece;
+    /// use number_reversi::game::assets::pieces::Pieces;
+    /// use number_reversi::game::rule::color::Color;
     /// 
     /// // add a black piece1
     /// let pieces = Pieces::make_pieces();
@@ -111,41 +126,68 @@ impl Pieces {
     /// 
     /// # Arguments
     /// 
-    /// * `piece_value` - the value of a piece. (1 <= piece_value <= 10)
+    /// * `piece_value` - the value of a piece. A value not in the set has no pieces.
     /// * `turn` - the turn of the player who has the pieces.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use number-reversi::game::assets::piece::Piece;
-    /// use number-reversi::game::assets::pieces::Pieces;
-    /// use number-reversi::game::rule::{color::Color, turn::Turn};
+    /// use number_reversi::game::assets::piece::Piece;
+    /// use number_reversi::game::assets::pieces::Pieces;
+    /// use number_reversi::game::assets::player::Player;
+    /// use number_reversi::game::rule::{color::Color, turn::Turn};
     /// 
     /// let pieces = Pieces::make_pieces();
-    /// let rest_num = pieces.get_rest_num(1, Turn{color: Color::Black});
+    /// let rest_num = pieces.get_rest_num(1, Turn{color: Color::Black, player: Player::Human});
     /// assert_eq!(rest_num, 5);
     /// ```
     pub fn get_rest_num(&self, piece_value: u8, turn: Turn) -> u8 {
         match turn.color {
-            Color::Black => *self.black.get(&piece_value).unwrap(),
-            Color::White => *self.white.get(&piece_value).unwrap(),
+            Color::Black => self.black.get(&piece_value).copied().unwrap_or(0),
+            Color::White => self.white.get(&piece_value).copied().unwrap_or(0),
         }
     }
 
-    pub fn select_piece(&self, turn: Turn) -> Piece {
+    /// is_exhausted returns whether no piece of any value is left for the turn.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use number_reversi::game::assets::pieces::Pieces;
+    /// use number_reversi::game::assets::player::Player;
+    /// use number_reversi::game::rule::{color::Color, turn::Turn};
+    /// 
+    /// let pieces = Pieces::make_pieces();
+    /// assert!(!pieces.is_exhausted(Turn{color: Color::Black, player: Player::Human}));
+    /// ```
+    pub fn is_exhausted(&self, turn: Turn) -> bool {
+        match turn.color {
+            Color::Black => self.black.values().all(|n| *n == 0),
+            Color::White => self.white.values().all(|n| *n == 0),
+        }
+    }
+
+    /// select_piece returns one of the remaining pieces of the turn, chosen by the `rng`.
+    /// The values are sorted before choosing, so the same `rng` always chooses the same piece.
+    pub fn select_piece(&self, turn: Turn, rng: &mut impl Rng) -> Piece {
         let pieces = match turn.color {
-            Color::Black => self.black.clone(),
-            Color::White => self.white.clone(),
+            Color::Black => &self.black,
+            Color::White => &self.white,
         };
-        let pieces: Vec<(&u8, &u8)> = pieces.iter()
+        let values = pieces.iter()
             .filter(|v| v.1 > &0)
-            .collect();
-        let i = rand::thread_rng().gen_range(0..pieces.len());
-        let value = *(pieces[i].0);
+            .map(|v| *v.0)
+            .collect::<BTreeSet<u8>>();
+        let i = rng.gen_range(0..values.len());
+        let value = *values.iter().nth(i).unwrap();
         Piece { color: turn.color, value }
     }
 }
 
+fn serialize_sorted<S: Serializer>(pieces: &HashMap<u8, u8>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_map(pieces.iter().collect::<BTreeMap<_, _>>())
+}
+
 
 
 
@@ -187,15 +229,29 @@ mod pieces_test {
                 piece::Piece, 
                 pieces::Pieces
             }, 
-            rule::color::Color
+            rule::{color::Color, moves::MoveError}
         };
         #[test]
         fn when_put_a_piece_black1_then_num_of_piece_black1_is_4() {
             let put_piece = Piece::new(Color::Black, 1);
             let pieces = Pieces::make_pieces();
-            let pieces = pieces.remove(&put_piece);
+            let pieces = pieces.remove(&put_piece).unwrap();
             assert_eq!(pieces.black.get(&1), Some(&4));
         }
+        #[test]
+        fn when_no_piece_of_the_value_is_left_then_returns_error() {
+            let put_piece = Piece::new(Color::White, 9);
+            let pieces = Pieces::make_pieces();
+            let pieces = pieces.remove(&put_piece).unwrap();
+            assert_eq!(pieces.remove(&put_piece), Err(MoveError::NoPiecesOfValue(put_piece)));
+            assert_eq!(pieces.white.get(&9), Some(&0));
+        }
+        #[test]
+        fn when_value_is_out_of_range_then_returns_error() {
+            let put_piece = Piece::new(Color::Black, 11);
+            let pieces = Pieces::make_pieces();
+            assert_eq!(pieces.remove(&put_piece), Err(MoveError::NoPiecesOfValue(put_piece)));
+        }
     }
     mod add_test {
         use crate::game::{
@@ -209,7 +265,7 @@ mod pieces_test {
         fn when_white_passed_then_black_max_piece_increase() {
             let put_piece = Piece::new(Color::Black, 1);
             let pieces = Pieces::make_pieces();
-            let pieces = pieces.remove(&put_piece);
+            let pieces = pieces.remove(&put_piece).unwrap();
             let pieces = pieces.add(&put_piece);
             assert_eq!(pieces.black.get(&1), Some(&5));
         }
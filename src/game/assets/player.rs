@@ -1,11 +1,50 @@
-use std::rc::Rc;
+use std::{fmt, rc::Rc};
 
+use serde::{Deserialize, Serialize};
 use yew::prelude::*;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use crate::game::rule::color::Color;
+
+use super::{level::Level, strategy_config::StrategyConfig};
+
+/// Represents who plays a color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Player {
     Human,
-    Com,
+    /// The computer, which chooses its moves with the `Strategy` of the `StrategyConfig`.
+    Com(StrategyConfig),
+}
+impl Player {
+    /// The COM which plays random moves.
+    pub const RANDOM_COM: Player = Player::Com(StrategyConfig::Random);
+
+    /// Returns whether the player is a COM, whatever its strategy is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::{player::Player, strategy_config::StrategyConfig};
+    ///
+    /// assert!(Player::Com(StrategyConfig::Greedy).is_com());
+    /// assert!(!Player::Human.is_com());
+    /// ```
+    pub fn is_com(&self) -> bool {
+        matches!(self, Player::Com(_))
+    }
+}
+
+/// Writes a COM with its level, such as `COM (Hard)`, or with its `StrategyConfig` when it is not the config of a level,
+/// such as `COM (AlphaBeta 4)`.
+impl fmt::Display for Player {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Player::Human => write!(f, "Human"),
+            Player::Com(config) => match Level::from_config(*config) {
+                Some(level) => write!(f, "COM ({})", level.name()),
+                None => write!(f, "COM ({})", config),
+            },
+        }
+    }
 }
 
 impl Reducible for Player {
@@ -14,4 +53,35 @@ impl Reducible for Player {
     fn reduce(self: Rc<Self>, action: Self::Action) -> Rc<Self> {
         action.into()
     }
-}
\ No newline at end of file
+}
+
+/// Represents who plays each color in a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Players {
+    pub black: Player,
+    pub white: Player,
+}
+impl Players {
+    /// Creates a new `Players` instance.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::player::{Player, Players};
+    /// use number_reversi::game::rule::color::Color;
+    ///
+    /// let players = Players::new(Player::Human, Player::RANDOM_COM);
+    /// assert_eq!(players.get(Color::White), Player::RANDOM_COM);
+    /// ```
+    pub fn new(black: Player, white: Player) -> Self {
+        Self { black, white }
+    }
+
+    /// Returns the `Player` who plays the specified `Color`.
+    pub fn get(&self, color: Color) -> Player {
+        match color {
+            Color::Black => self.black,
+            Color::White => self.white,
+        }
+    }
+}
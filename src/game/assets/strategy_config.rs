@@ -0,0 +1,74 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+/// `StrategyConfig` is the choice of a COM strategy and its parameters, carried by `Player::Com`.
+/// The `Strategy` itself is built from it in `controller::com::strategy`.
+///
+/// The config is written as the name of the strategy followed by its parameters, such as `AlphaBeta 3 50000`,
+/// where an `AlphaBeta` without a node budget is written without the last number.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::assets::strategy_config::StrategyConfig;
+///
+/// let config = StrategyConfig::AlphaBeta { depth: 4, node_budget: None };
+/// assert_eq!(config.to_string(), "AlphaBeta 4");
+/// assert_eq!("AlphaBeta 4".parse::<StrategyConfig>(), Ok(config));
+/// assert_eq!("Mcts 300".parse::<StrategyConfig>(), Ok(StrategyConfig::Mcts { iterations: 300 }));
+/// assert!("Mcts".parse::<StrategyConfig>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum StrategyConfig {
+    /// Puts a random piece on a random puttable square.
+    #[default]
+    Random,
+    /// Looks one move ahead with `Greedy`.
+    Greedy,
+    /// Searches with `AlphaBeta`.
+    AlphaBeta { depth: usize, node_budget: Option<usize> },
+    /// Searches with `Mcts`.
+    Mcts { iterations: usize },
+}
+
+impl fmt::Display for StrategyConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StrategyConfig::Random => write!(f, "Random"),
+            StrategyConfig::Greedy => write!(f, "Greedy"),
+            StrategyConfig::AlphaBeta { depth, node_budget: None } => write!(f, "AlphaBeta {}", depth),
+            StrategyConfig::AlphaBeta { depth, node_budget: Some(nodes) } => write!(f, "AlphaBeta {} {}", depth, nodes),
+            StrategyConfig::Mcts { iterations } => write!(f, "Mcts {}", iterations),
+        }
+    }
+}
+
+impl FromStr for StrategyConfig {
+    type Err = ParseStrategyConfigError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseStrategyConfigError(s.to_string());
+        let mut words = s.split(' ');
+        let name = words.next().ok_or_else(invalid)?;
+        let numbers = words.map(|word| word.parse::<usize>().map_err(|_| invalid())).collect::<Result<Vec<_>, _>>()?;
+        match (name, numbers.as_slice()) {
+            ("Random", []) => Ok(StrategyConfig::Random),
+            ("Greedy", []) => Ok(StrategyConfig::Greedy),
+            ("AlphaBeta", [depth]) => Ok(StrategyConfig::AlphaBeta { depth: *depth, node_budget: None }),
+            ("AlphaBeta", [depth, nodes]) => Ok(StrategyConfig::AlphaBeta { depth: *depth, node_budget: Some(*nodes) }),
+            ("Mcts", [iterations]) => Ok(StrategyConfig::Mcts { iterations: *iterations }),
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// The error returned when a string is not a `StrategyConfig` in its written form.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseStrategyConfigError(pub String);
+impl fmt::Display for ParseStrategyConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a COM strategy", self.0)
+    }
+}
+impl Error for ParseStrategyConfigError {}
@@ -1,4 +1,4 @@
 pub mod put_piece;
-pub mod check_puttable_position_exists;
 pub mod reverse;
-pub mod check_pass;
\ No newline at end of file
+pub mod check_pass;
+pub mod check_game_over;
\ No newline at end of file
@@ -0,0 +1,131 @@
+use crate::game::{
+    assets::{board::Board, pieces::Pieces, player::Players},
+    rule::{
+        result::{EndReason, GameResult},
+        turn::Turn
+    }
+};
+
+use super::check_pass::can_move;
+
+/// Check if the game has ended, and returns the final result if so.
+/// 
+/// The game ends when one of the following holds, checked in this order:
+/// 
+/// 1. Every square of the board has a piece.
+/// 2. Either player has no pieces left, the player of the current turn being checked first.
+/// 3. Neither player can put a piece on the board.
+/// 
+/// # Arguments
+/// 
+/// * `board` - The current state of the game board.
+/// * `turn` - The current player's turn.
+/// * `pieces` - The remaining pieces of each color.
+/// * `players` - The players of each color.
+/// 
+/// # Returns
+/// 
+/// Returns the `GameResult` if the game has ended, otherwise `None`.
+/// 
+/// # Example
+/// 
+/// ```
+/// use number_reversi::game::assets::{board::Board, pieces::Pieces, player::{Player, Players}};
+/// use number_reversi::game::rule::{turn::Turn, color::Color};
+/// use number_reversi::game::events::check_game_over::check_game_over;
+/// 
+/// let players = Players::new(Player::Human, Player::Human);
+/// let turn = Turn{color: Color::Black, player: Player::Human};
+/// 
+/// assert_eq!(check_game_over(&Board::set_initial_state(), turn, &Pieces::make_pieces(), players), None);
+/// ```
+pub fn check_game_over(board: &Board, turn: Turn, pieces: &Pieces, players: Players) -> Option<GameResult> {
+    let next_turn = turn.change(players);
+    let reason = if board.is_full() {
+        EndReason::BoardFull
+    } else if let Some(exhausted) = [turn, next_turn].into_iter().find(|t| pieces.is_exhausted(*t)) {
+        EndReason::PiecesExhausted(exhausted.color)
+    } else if !can_move(board, turn, pieces) && !can_move(board, next_turn, pieces) {
+        EndReason::NoMoves
+    } else {
+        return None;
+    };
+    Some(GameResult::new(board.get_score(), reason))
+}
+
+#[cfg(test)]
+mod check_game_over_test {
+    use crate::{
+        game::{
+            assets::{board::Board, pieces::Pieces, piece::Piece, player::{Player, Players}, square::Square},
+            rule::{color::Color, result::{EndReason, GameResult}, score::Score, turn::Turn}
+        },
+        tests::helper::board_helper::BoardHelper
+    };
+
+    use super::check_game_over;
+
+    fn black_turn() -> Turn {
+        Turn{color: Color::Black, player: Player::Human}
+    }
+
+    fn players() -> Players {
+        Players::new(Player::Human, Player::Human)
+    }
+
+    #[test]
+    fn when_game_starts_then_game_is_not_over() {
+        let result = check_game_over(&Board::set_initial_state(), black_turn(), &Pieces::make_pieces(), players());
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn when_board_is_full_then_game_is_over() {
+        let mut board = Board {
+            squares: vec![vec![Square::Put(Piece::new(Color::Black, 2)); 8]; 8]
+        };
+        board.squares[0][0] = Square::Put(Piece::new(Color::White, 10));
+        let result = check_game_over(&board, black_turn(), &Pieces::make_pieces(), players());
+        assert_eq!(result, Some(GameResult{
+            winner: Some(Color::Black),
+            black_score: 126,
+            white_score: 10,
+            reason: EndReason::BoardFull,
+        }));
+    }
+
+    #[test]
+    fn when_nobody_can_put_then_game_is_over() {
+        let board = BoardHelper::make_board(vec![(0, 0, 3)], vec![(7, 7, 3)]);
+        let result = check_game_over(&board, black_turn(), &Pieces::make_pieces(), players());
+        assert_eq!(result, Some(GameResult::new(Score{black: 3, white: 3}, EndReason::NoMoves)));
+        assert_eq!(result.unwrap().winner, None);
+    }
+
+    #[test]
+    fn when_only_opponent_can_put_then_game_is_not_over() {
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let result = check_game_over(&board, black_turn(), &Pieces::make_pieces(), players());
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn when_player_has_no_pieces_left_then_game_is_over() {
+        let mut pieces = Pieces::make_pieces();
+        pieces.black.values_mut().for_each(|n| *n = 0);
+        let result = check_game_over(&Board::set_initial_state(), black_turn(), &pieces, players());
+        assert_eq!(result.map(|r| r.reason), Some(EndReason::PiecesExhausted(Color::Black)));
+    }
+
+    #[test]
+    fn when_opponent_has_no_pieces_left_then_game_is_over() {
+        let mut pieces = Pieces::make_pieces();
+        pieces.white.values_mut().for_each(|n| *n = 0);
+        let result = check_game_over(&Board::set_initial_state(), black_turn(), &pieces, players());
+        assert_eq!(result.map(|r| r.reason), Some(EndReason::PiecesExhausted(Color::White)));
+
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let result = check_game_over(&board, black_turn(), &pieces, players());
+        assert_eq!(result.map(|r| r.reason), Some(EndReason::PiecesExhausted(Color::White)));
+    }
+}
@@ -1,18 +1,22 @@
-use crate::{game::{
-    rule::turn::Turn, 
-    assets::{board::Board, piece::UsedPiece, pieces::Pieces}
-}, app::game::GameModeProps};
-
-use super::check_puttable_position_exists::check_puttable_position_exists;
+use crate::game::{
+    rule::{turn::Turn, legal_moves::LegalMoves}, 
+    assets::{bit_board::BitBoard, board::Board, piece::UsedPiece, pieces::Pieces, player::Players}
+};
 
 /// The `check_pass` function is used to determine if a move can be made or 
 /// if it's time to pass the turn to the next player.
 /// 
-/// If a puttable position exists, the function will return the current turn, 
-/// the `UsedPiece` list, the `Pieces` list, and the current `Board` state as is.
+/// If the player of the current turn can move, the function will return the current turn, 
+/// the `UsedPiece` list and the `Pieces` list as is.
+/// 
+/// If the player cannot move but the next player can, the function will change the turn to the next player, 
+/// remove a piece from the `UsedPiece` list, and add it to the `Pieces` list.
+/// The piece is the highest-valued piece the next player has used. If the next player has not used 
+/// any piece yet, the turn is passed without a penalty. A value is given back at most once until it is used again,
+/// since `UsedPiece` records the values used rather than every piece.
 /// 
-/// If a puttable position does not exist, the function will change the turn to the next player, 
-/// remove a piece from the `UsedPiece` list, add it to the `Pieces` list, and update the current `Board` state.
+/// If neither player can move, or either player has no pieces left, the game is over and nothing is changed.
+/// So a player who is out of pieces never passes, and the opponent gets no piece back for it.
 /// 
 /// # Arguments
 /// 
@@ -20,44 +24,72 @@ use super::check_puttable_position_exists::check_puttable_position_exists;
 /// * `board` - The current game board.
 /// * `used` - A list of pieces that have already been placed on the board.
 /// * `pieces` - A list of pieces that have not yet been placed on the board.
+/// * `players` - The players of each color.
 /// 
 /// # Returns
 /// 
-/// A tuple containing the updated turn, `UsedPiece` list, `Pieces` list, and `Board` state.
+/// A tuple containing the updated turn, `UsedPiece` list, and `Pieces` list.
 /// 
 /// # Example
 /// 
 /// ```
-/// use number-reversi::game::assets::pieces::Pieces;
-/// use number-reversi::game::assets::piece::Piece;
-/// use number-reversi::game::assets::piece::UsedPiece;
-/// use number-reversi::game::assets::board::Board;
-/// use number-reversi::game::rule::{turn::Turn, color::Color};
+/// use number_reversi::game::assets::pieces::Pieces;
+/// use number_reversi::game::assets::piece::Piece;
+/// use number_reversi::game::assets::piece::UsedPiece;
+/// use number_reversi::game::assets::board::Board;
+/// use number_reversi::game::assets::player::{Player, Players};
+/// use number_reversi::game::rule::{turn::Turn, color::Color};
 /// 
-/// use number-reversi::game::events::check_pass::check_pass;
+/// use number_reversi::game::events::check_pass::check_pass;
 /// 
-/// let turn = Turn{color: Color::Black};
+/// let players = Players::new(Player::Human, Player::Human);
+/// let turn = Turn{color: Color::Black, player: Player::Human};
 /// let board = Board::set_initial_state();
 /// let used = UsedPiece::new().add_piece(
 ///     Piece::new(Color::White, 1)).add_piece(Piece::new(Color::Black, 1));
 /// let pieces = Pieces::make_pieces();
 /// 
-/// let (new_turn, new_used, new_pieces, new_board) = check_pass(turn, board, used, pieces);
+/// let (new_turn, new_used, new_pieces) = check_pass(turn, &board, used, pieces, players);
+/// 
+/// // black can put a piece, so the turn is not passed
+/// assert_eq!(new_turn, turn);
 /// ```
-pub fn check_pass(turn: Turn, board: Board, used: UsedPiece, pieces: Pieces, game_mode_props: GameModeProps) 
--> (Turn, UsedPiece, Pieces, Board) {
-    if board.has_puttable() {
-        (turn, used, pieces, board)
-    } else {
-        let turn = turn.change(game_mode_props);
-        let add_piece = used.get_add_piece(turn);
-        let used = used.remove_piece(add_piece);
-        let pieces = pieces.add(&add_piece);
-        let board = check_puttable_position_exists(board, turn);
-        (turn, used, pieces, board)
+pub fn check_pass(turn: Turn, board: &Board, used: UsedPiece, pieces: Pieces, players: Players) 
+-> (Turn, UsedPiece, Pieces) {
+    if can_move(board, turn, &pieces) {
+        return (turn, used, pieces);
+    }
+
+    let next_turn = turn.change(players);
+    if !can_move(board, next_turn, &pieces) || pieces.is_exhausted(turn) {
+        return (turn, used, pieces);
+    }
+
+    match used.get_add_piece(next_turn) {
+        Some(add_piece) => {
+            let used = used.remove_piece(add_piece);
+            let pieces = pieces.add(&add_piece);
+            (next_turn, used, pieces)
+        },
+        None => (next_turn, used, pieces),
     }
 }
 
+/// Returns whether the player of the turn can put a piece.
+/// 
+/// # Arguments
+/// 
+/// * `board` - The current game board.
+/// * `turn` - The player's turn.
+/// * `pieces` - The remaining pieces of each color.
+pub fn can_move(board: &Board, turn: Turn, pieces: &Pieces) -> bool {
+    let puttable = match BitBoard::try_from(board) {
+        Ok(bit_board) => bit_board.legal_moves(turn.color) != 0,
+        Err(_) => !LegalMoves::new(board, turn).is_empty(),
+    };
+    puttable && !pieces.is_exhausted(turn)
+}
+
 
 #[cfg(test)]
 mod check_pass_test {
@@ -66,16 +98,16 @@ mod check_pass_test {
     use crate::{
         tests::helper::board_helper::BoardHelper, 
         game::{
-            rule::{turn::Turn, color::Color, position::{ReversibleCandidates, Position}}, 
-            assets::{pieces::Pieces, piece::{UsedPiece, Piece}, square::Square, player::Player}
-        }, app::game::GameModeProps
+            rule::{turn::Turn, color::Color, legal_moves::LegalMoves, position::{ReversibleCandidates, Position}}, 
+            assets::{pieces::Pieces, piece::{UsedPiece, Piece}, player::{Player, Players}}
+        }
     };
 
     use super::check_pass;
 
     #[test]
     fn when_no_puttable_square_then_pass() {
-        let turn =Turn{color: Color::Black, player: Player::Human};
+        let turn = Turn{color: Color::Black, player: Player::Human};
         let board = BoardHelper::make_board(
             vec![
                 (1, 1, 1)], 
@@ -86,15 +118,91 @@ mod check_pass_test {
         let used = UsedPiece::new();
         let used = used.add_piece(Piece::new(Color::White, 1));
 
-        let props = GameModeProps{black: Player::Human, white: Player::Human};
-        let (new_turn, used, pieces, board) = check_pass(turn, board, used, pieces, props);
+        let players = Players::new(Player::Human, Player::Human);
+        let (new_turn, used, pieces) = check_pass(turn, &board, used, pieces, players);
         assert_eq!(new_turn, Turn{color: Color::White, player: Player::Human});
         assert_eq!(used.piece_set, HashSet::new());
         assert_eq!(pieces.get_rest_num(1, new_turn), 6);
-        assert_eq!(board.squares[0][2], Square::Puttable(vec![
+        assert_eq!(LegalMoves::new(&board, new_turn).get(Position{x: 0, y: 2}), Some(&vec![
             ReversibleCandidates::new(
                 Position{x: 2, y: 0},
                 vec![Position{x: 1, y: 1}])
             ]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn when_opponent_has_not_used_any_piece_then_pass_without_penalty() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let pieces = Pieces::make_pieces();
+        let used = UsedPiece::new().add_piece(Piece::new(Color::Black, 4));
+
+        let players = Players::new(Player::Human, Player::Human);
+        let (new_turn, new_used, new_pieces) = check_pass(turn, &board, used.clone(), pieces.clone(), players);
+        assert_eq!(new_turn.color, Color::White);
+        assert_eq!(new_used, used);
+        assert_eq!(new_pieces, pieces);
+    }
+
+    #[test]
+    fn when_opponent_has_used_a_value_twice_then_it_is_given_back_once() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let used = UsedPiece::new()
+            .add_piece(Piece::new(Color::White, 5))
+            .add_piece(Piece::new(Color::White, 5))
+            .add_piece(Piece::new(Color::White, 3));
+
+        let players = Players::new(Player::Human, Player::Human);
+        let (_, used, pieces) = check_pass(turn, &board, used, Pieces::make_pieces(), players);
+        let (_, used, pieces) = check_pass(turn, &board, used, pieces, players);
+        assert_eq!(pieces.white.get(&5), Some(&4));
+        assert_eq!(pieces.white.get(&3), Some(&5));
+        assert_eq!(used, UsedPiece::new());
+    }
+
+    #[test]
+    fn when_nobody_can_put_then_turn_is_kept() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = BoardHelper::make_board(vec![(0, 0, 1)], vec![(7, 7, 1)]);
+        let pieces = Pieces::make_pieces();
+        let used = UsedPiece::new()
+            .add_piece(Piece::new(Color::White, 1))
+            .add_piece(Piece::new(Color::Black, 1));
+
+        let players = Players::new(Player::Human, Player::Human);
+        let (new_turn, new_used, new_pieces) = check_pass(turn, &board, used.clone(), pieces.clone(), players);
+        assert_eq!(new_turn, turn);
+        assert_eq!(new_used, used);
+        assert_eq!(new_pieces, pieces);
+    }
+
+    #[test]
+    fn when_opponent_has_no_pieces_left_then_turn_is_kept() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let mut pieces = Pieces::make_pieces();
+        pieces.white.values_mut().for_each(|n| *n = 0);
+        let used = UsedPiece::new().add_piece(Piece::new(Color::White, 1));
+
+        let players = Players::new(Player::Human, Player::Human);
+        let (new_turn, new_used, _) = check_pass(turn, &board, used.clone(), pieces, players);
+        assert_eq!(new_turn, turn);
+        assert_eq!(new_used, used);
+    }
+
+    #[test]
+    fn when_player_has_no_pieces_left_then_no_penalty_is_given() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = BoardHelper::make_board(vec![(1, 1, 1)], vec![(2, 0, 1)]);
+        let mut pieces = Pieces::make_pieces();
+        pieces.black.values_mut().for_each(|n| *n = 0);
+        let used = UsedPiece::new().add_piece(Piece::new(Color::White, 1));
+
+        let players = Players::new(Player::Human, Player::Human);
+        let (new_turn, new_used, new_pieces) = check_pass(turn, &board, used.clone(), pieces.clone(), players);
+        assert_eq!(new_turn, turn);
+        assert_eq!(new_used, used);
+        assert_eq!(new_pieces, pieces);
+    }
+}
@@ -1,8 +1,7 @@
-use crate::{game::{rule::position::Position, assets::{piece::{Piece, UsedPiece}, board::Board, pieces::Pieces}}, app::game::GameModeProps};
-use crate::game::rule::turn::Turn;
-use crate::game::assets::square::Square;
+use crate::game::{rule::position::Position, assets::{piece::{Piece, UsedPiece}, board::Board, pieces::Pieces, player::Players}};
+use crate::game::rule::{flip_rule::FlipRule, legal_moves::LegalMoves, moves::MoveError, turn::Turn};
 
-use super::{check_puttable_position_exists::check_puttable_position_exists, reverse::reverse, check_pass::check_pass};
+use super::{reverse::reverse, check_pass::check_pass};
 
 /// The `put_piece` function updates the state of the game board, pieces, and used pieces
 /// when a piece is placed at a given position.
@@ -14,33 +13,39 @@ use super::{check_puttable_position_exists::check_puttable_position_exists, reve
 /// * `turn` - The current turn
 /// * `pieces` - The remaining pieces of each color
 /// * `used` - The pieces that have already been placed
+/// * `players` - The players of each color
+/// * `rule` - The `FlipRule` deciding which sandwiched pieces are reversed
 /// 
 /// # Returns
-/// A tuple containing the updated board, turn, pieces, piece, and used pieces.
+/// A tuple containing the updated board, turn, pieces, and used pieces.
+/// 
+/// # Errors
+/// Returns a `MoveError` without changing anything when the piece cannot be placed.
 /// 
 /// # Example
 /// ```
-/// use number-reversi::game::assets::{board::Board, pieces::Pieces, piece::{Piece, UsedPiece}};
-/// use number-reversi::game::rule::{position::Position, turn::Turn, color::Color};
-/// use number-reversi::game::events::put_piece::put_piece;
-/// 
-/// use number-reversi::game::events::check_puttable_position_exists::check_puttable_position_exists;
+/// use number_reversi::game::assets::{board::Board, pieces::Pieces, piece::{Piece, UsedPiece}};
+/// use number_reversi::game::assets::player::{Player, Players};
+/// use number_reversi::game::rule::{position::Position, turn::Turn, color::Color, flip_rule::BuiltinFlipRule};
+/// use number_reversi::game::events::put_piece::put_piece;
 /// 
-/// let turn = Turn{color: Color::Black};
-/// let board = check_puttable_position_exists(Board::set_initial_state(), turn);
+/// let players = Players::new(Player::Human, Player::Human);
+/// let turn = Turn{color: Color::Black, player: Player::Human};
+/// let board = Board::set_initial_state();
 /// let pieces = Pieces::make_pieces();
 /// let piece = Piece::new(Color::Black, 1);
 /// let used = UsedPiece::new();
 /// 
 /// let position = Position { x: 4, y: 2 };
-/// let (updated_board, updated_turn, updated_pieces, updated_piece, updated_used) = put_piece(position, piece, board.clone(), turn, pieces.clone(), used.clone());
+/// let (updated_board, updated_turn, updated_pieces, updated_used) = 
+///     put_piece(position, piece, board.clone(), turn, pieces.clone(), used.clone(), players, &BuiltinFlipRule::StrictSum).unwrap();
 /// 
 /// assert_ne!(board, updated_board);
 /// assert_ne!(turn, updated_turn);
 /// assert_ne!(pieces, updated_pieces);
-/// assert_ne!(piece, updated_piece);
 /// assert_ne!(used, updated_used);
 /// ```
+#[allow(clippy::too_many_arguments)]
 pub fn put_piece(
     position: Position, 
     piece: Piece, 
@@ -48,36 +53,38 @@ pub fn put_piece(
     turn: Turn, 
     pieces: Pieces, 
     used: UsedPiece,
-    game_mode_props: GameModeProps,
-) -> (Board, Turn, Pieces, Piece, UsedPiece) {
-    match &board.squares[position.x][position.y] {
-        Square::Puttable(c) => {
-            let board = board.put_piece(position, piece);
-            let board = reverse(board, piece, c.to_vec());
-            let board = check_puttable_position_exists(board, turn);
-            
-            let pieces = pieces.remove(&piece);
-            let used = used.add_piece(piece);
-
-            let turn = turn.change(game_mode_props);
-            let board = check_puttable_position_exists(board, turn);
-
-            let (turn, used, pieces, board) = check_pass(turn, board, used, pieces, game_mode_props);
-            (board, turn, pieces, Piece::new(turn.color.clone(), 1), used)
-        },
-        _ => (
-            board, turn, pieces, piece, used
-        ),
+    players: Players,
+    rule: &impl FlipRule,
+) -> Result<(Board, Turn, Pieces, UsedPiece), MoveError> {
+    if piece.color != turn.color {
+        return Err(MoveError::WrongTurn(piece.color));
+    }
+    if !board.contains(position) {
+        return Err(MoveError::OutOfBounds(position));
     }
+    let legal_moves = LegalMoves::new(&board, turn);
+    let Some(c) = legal_moves.get(position) else {
+        return Err(MoveError::SquareNotPuttable(position));
+    };
+    let pieces = pieces.remove(&piece)?;
+    let board = board.put_piece(position, piece);
+    let board = reverse(board, piece, c.to_vec(), rule);
+
+    let used = used.add_piece(piece);
+
+    let turn = turn.change(players);
+
+    let (turn, used, pieces) = check_pass(turn, &board, used, pieces, players);
+    Ok((board, turn, pieces, used))
 }
 
 #[cfg(test)]
 mod tests {
     use crate::{game::{
-        assets::{board::Board, pieces::Pieces, piece::{Piece, UsedPiece}, player::Player},
-        rule::{position::{Position, ReversibleCandidates}, color::Color},
-        rule::turn::Turn, events::check_puttable_position_exists::check_puttable_position_exists,
-    }, tests::helper::board_helper::BoardHelper, app::game::GameModeProps};
+        assets::{board::Board, pieces::Pieces, piece::{Piece, UsedPiece}, player::{Player, Players}},
+        rule::{position::{Position, ReversibleCandidates}, color::Color, moves::MoveError},
+        rule::{turn::Turn, flip_rule::BuiltinFlipRule, legal_moves::LegalMoves},
+    }, tests::helper::board_helper::BoardHelper};
     use crate::game::assets::square::Square;
     use crate::game::events::put_piece::put_piece;
 
@@ -87,16 +94,13 @@ mod tests {
         let used = UsedPiece::new();
 
         let board = Board::set_initial_state();
-        let props = GameModeProps{black: Player::Human, white: Player::Human};
+        let players = Players::new(Player::Human, Player::Human);
         let turn = Turn{color: Color::Black, player: Player::Human};
-        let piece = Piece::new(turn.color.clone(), 1);
+        let piece = Piece::new(turn.color, 1);
         let position = Position{ x: 4, y: 2 };
 
-
-        let board = check_puttable_position_exists(board, turn);
-
-        let (new_board, new_turn, new_pieces, new_piece, new_used) = 
-            put_piece(position, piece, board, turn, pieces, used, props);
+        let (new_board, new_turn, new_pieces, _) = 
+            put_piece(position, piece, board, turn, pieces, used, players, &BuiltinFlipRule::StrictSum).unwrap();
 
         assert_eq!(
             new_board.squares[position.x][position.y], 
@@ -116,18 +120,81 @@ mod tests {
         let used = UsedPiece::new();
         let turn = Turn{color: Color::Black, player: Player::Human};
 
-        let board = check_puttable_position_exists(board, turn);
-        let piece = Piece::new(turn.color.clone(), 1);
+        let piece = Piece::new(turn.color, 1);
         let position = Position { x: 2, y: 0 };
 
-        let props = GameModeProps{black: Player::Human, white: Player::Human};
+        let players = Players::new(Player::Human, Player::Human);
 
-        let (new_board, new_turn, new_pieces, new_piece, new_used) = 
-            put_piece(position, piece, board, turn, pieces, used, props);
+        let (new_board, new_turn, _, _) = 
+            put_piece(position, piece, board, turn, pieces, used, players, &BuiltinFlipRule::StrictSum).unwrap();
 
         assert_eq!(new_turn, turn);
-        assert_eq!(new_board.squares[0][2], Square::Puttable(vec![
+        assert_eq!(LegalMoves::new(&new_board, new_turn).get(Position{x: 0, y: 2}), Some(&vec![
             ReversibleCandidates::new(Position{x: 0, y: 0}, vec![Position{x: 0, y: 1}]),
         ]));
     }
+
+    #[test]
+    fn when_flip_rule_rejects_the_sandwiched_piece_then_it_is_not_reversed() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let players = Players::new(Player::Human, Player::Human);
+        let piece = Piece::new(Color::Black, 1);
+
+        let (new_board, _, _, _) = put_piece(
+            Position{x: 4, y: 2}, piece, Board::set_initial_state(), turn, Pieces::make_pieces(), UsedPiece::new(),
+            players, &BuiltinFlipRule::Difference).unwrap();
+        assert_eq!(new_board.squares[4][2], Square::Put(piece));
+        assert_eq!(new_board.squares[4][3], Square::Put(Piece::new(Color::White, 1)));
+    }
+
+    #[test]
+    fn when_square_is_not_puttable_then_returns_error() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = Board::set_initial_state();
+        let players = Players::new(Player::Human, Player::Human);
+        let piece = Piece::new(Color::Black, 1);
+
+        let result = put_piece(
+            Position{x: 0, y: 0}, piece, board.clone(), turn, Pieces::make_pieces(), UsedPiece::new(), players, &BuiltinFlipRule::StrictSum);
+        assert_eq!(result, Err(MoveError::SquareNotPuttable(Position{x: 0, y: 0})));
+
+        let result = put_piece(
+            Position{x: 3, y: 3}, piece, board, turn, Pieces::make_pieces(), UsedPiece::new(), players, &BuiltinFlipRule::StrictSum);
+        assert_eq!(result, Err(MoveError::SquareNotPuttable(Position{x: 3, y: 3})));
+    }
+
+    #[test]
+    fn when_position_is_out_of_board_then_returns_error() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = Board::set_initial_state();
+        let players = Players::new(Player::Human, Player::Human);
+
+        let result = put_piece(
+            Position{x: 8, y: 2}, Piece::new(Color::Black, 1), board, turn, Pieces::make_pieces(), UsedPiece::new(), players, &BuiltinFlipRule::StrictSum);
+        assert_eq!(result, Err(MoveError::OutOfBounds(Position{x: 8, y: 2})));
+    }
+
+    #[test]
+    fn when_piece_is_opponents_then_returns_error() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = Board::set_initial_state();
+        let players = Players::new(Player::Human, Player::Human);
+
+        let result = put_piece(
+            Position{x: 4, y: 2}, Piece::new(Color::White, 1), board, turn, Pieces::make_pieces(), UsedPiece::new(), players, &BuiltinFlipRule::StrictSum);
+        assert_eq!(result, Err(MoveError::WrongTurn(Color::White)));
+    }
+
+    #[test]
+    fn when_no_piece_of_the_value_is_left_then_returns_error() {
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let board = Board::set_initial_state();
+        let players = Players::new(Player::Human, Player::Human);
+        let piece = Piece::new(Color::Black, 10);
+        let pieces = Pieces::make_pieces().remove(&piece).unwrap();
+
+        let result = put_piece(
+            Position{x: 4, y: 2}, piece, board, turn, pieces, UsedPiece::new(), players, &BuiltinFlipRule::StrictSum);
+        assert_eq!(result, Err(MoveError::NoPiecesOfValue(piece)));
+    }
 }
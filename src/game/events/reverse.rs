@@ -3,7 +3,7 @@ use crate::game::{
         board::Board, piece::Piece, 
         square::Square
     }, 
-    rule::position::ReversibleCandidates
+    rule::{flip_rule::FlipRule, position::{Position, ReversibleCandidates}}
 };
 
 /// Reverses the `Piece`s in the specified positions.
@@ -13,46 +13,81 @@ use crate::game::{
 /// * `board` - the current state of the game board
 /// * `piece` - the `Piece` that was just placed on the board
 /// * `candidates` - the positions of the `Piece`s to be reversed
+/// * `rule` - the `FlipRule` deciding which of the candidates are reversed
 /// 
 /// # Returns
 /// 
 /// Returns the updated state of the game board with the reversed `Piece`s.
-pub fn reverse(board: Board, piece: Piece, candidates: Vec<ReversibleCandidates>) -> Board {
+pub fn reverse(board: Board, piece: Piece, candidates: Vec<ReversibleCandidates>, rule: &impl FlipRule) -> Board {
     let mut squares = board.squares.clone();
 
-    for c in candidates.into_iter() {
-        if let Square::Put(opposite) = board.squares[c.opposite.x][c.opposite.y] {
-            let some_of_both_ends = piece.value + opposite.value;
-            for p in c.positions.into_iter() {
-                if let Square::Put(r) = board.squares[p.x][p.y] {
-                    if r.value < some_of_both_ends {
-                        squares[p.x][p.y] = Square::Put(r.reverse());
-                    }
-                }
-            }
-        };
+    for p in flipped_positions(&board, piece, &candidates, rule) {
+        if let Square::Put(r) = board.squares[p.x][p.y] {
+            squares[p.x][p.y] = Square::Put(r.reverse());
+        }
     }
     Board{squares}
 }
 
+/// Returns the positions of the `Piece`s that `reverse` would reverse, without changing the board.
+///
+/// # Example
+///
+/// ```
+/// use number_reversi::game::assets::{board::Board, piece::Piece, player::Player};
+/// use number_reversi::game::events::reverse::flipped_positions;
+/// use number_reversi::game::rule::{
+///     color::Color, flip_rule::BuiltinFlipRule, legal_moves::LegalMoves, position::Position, turn::Turn
+/// };
+///
+/// let board = Board::set_initial_state();
+/// let turn = Turn{color: Color::Black, player: Player::Human};
+/// let position = Position{x: 4, y: 2};
+/// let candidates = LegalMoves::new(&board, turn).get(position).unwrap().clone();
+///
+/// let piece = Piece::new(Color::Black, 1);
+/// assert_eq!(flipped_positions(&board, piece, &candidates, &BuiltinFlipRule::StrictSum), vec![Position{x: 4, y: 3}]);
+/// assert_eq!(flipped_positions(&board, piece, &candidates, &BuiltinFlipRule::Difference), vec![]);
+/// ```
+pub fn flipped_positions(
+    board: &Board,
+    piece: Piece,
+    candidates: &[ReversibleCandidates],
+    rule: &impl FlipRule,
+) -> Vec<Position> {
+    candidates.iter().flat_map(|c| {
+        let opposite = match board.squares[c.opposite.x][c.opposite.y] {
+            Square::Put(opposite) => Some(opposite.value),
+            Square::Empty => None,
+        };
+        c.positions.iter().filter(move |p| match (&board.squares[p.x][p.y], opposite) {
+            (Square::Put(r), Some(opposite)) => rule.flips(r.value, piece.value, opposite),
+            _ => false,
+        })
+    })
+    .copied()
+    .collect()
+}
+
 #[cfg(test)]
 mod reverse_test {
-    use crate::game::{assets::{board::Board, piece::Piece, square::Square, player::Player}, events::check_puttable_position_exists::check_puttable_position_exists, rule::{turn::Turn, color::Color, position::{Position, ReversibleCandidates}}};
+    use crate::game::{assets::{board::Board, piece::Piece, square::Square, player::Player}, rule::{flip_rule::BuiltinFlipRule, legal_moves::LegalMoves, turn::Turn, color::Color, position::{Position, ReversibleCandidates}}};
 
     use super::reverse;
 
     #[test]
     fn test() {
         let turn = Turn{color: Color::Black, player: Player::Human};
-        let board = check_puttable_position_exists(Board::set_initial_state(), turn);
+        let board = Board::set_initial_state();
         let position = Position{x: 4, y: 2};
         let piece = Piece::new(Color::Black, 1);
-        let candidates = vec![
+        let candidates = LegalMoves::new(&board, turn).get(position).unwrap().clone();
+        assert_eq!(candidates, vec![
             ReversibleCandidates{
                 opposite: Position{x: 4, y: 4}, 
-                positions: vec![Position{x: 4, y: 3}]}];
+                positions: vec![Position{x: 4, y: 3}]}]);
         let board = board.put_piece(position, piece);
-        let board = reverse(board, piece, candidates);
+        let board = reverse(board, piece, candidates, &BuiltinFlipRule::StrictSum);
         assert_eq!(board.squares[4][3], Square::Put(Piece::new(Color::Black, 1)));
     }
 }
\ No newline at end of file
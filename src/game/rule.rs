@@ -2,4 +2,10 @@ pub mod color;
 pub mod position;
 pub mod score;
 pub mod turn;
-pub mod search;
\ No newline at end of file
+pub mod search;
+pub mod moves;
+pub mod result;
+pub mod legal_moves;
+pub mod rules;
+pub mod flip_rule;
+pub mod symmetry;
\ No newline at end of file
@@ -1,6 +1,26 @@
+use serde::{Deserialize, Serialize};
+
 /// A type that represents the color of a `Piece`.
-#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy, Serialize, Deserialize)]
 pub enum Color {
     Black,
     White,
-}
\ No newline at end of file
+}
+impl Color {
+    /// Returns the color of the opponent.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::rule::color::Color;
+    ///
+    /// assert_eq!(Color::Black.opponent(), Color::White);
+    /// assert_eq!(Color::White.opponent(), Color::Black);
+    /// ```
+    pub fn opponent(&self) -> Self {
+        match self {
+            Color::Black => Color::White,
+            Color::White => Color::Black,
+        }
+    }
+}
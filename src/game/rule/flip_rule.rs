@@ -0,0 +1,104 @@
+use serde::{Deserialize, Serialize};
+
+/// `FlipRule` decides whether a sandwiched piece is reversed.
+///
+/// A piece can be put wherever it sandwiches opponent pieces, whatever the rule is.
+/// The rule only decides which of the sandwiched pieces are reversed.
+pub trait FlipRule {
+    /// Returns whether a sandwiched piece of the value `sandwiched` is reversed
+    /// when a piece of the value `put` closes the line with a piece of the value `opposite`.
+    fn flips(&self, sandwiched: u8, put: u8, opposite: u8) -> bool;
+}
+
+/// The built-in `FlipRule`s, one of which is chosen for each game.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::rule::flip_rule::{BuiltinFlipRule, FlipRule};
+///
+/// // a sandwiched 5 between 2 and 3
+/// assert!(!BuiltinFlipRule::StrictSum.flips(5, 2, 3));
+/// assert!(BuiltinFlipRule::InclusiveSum.flips(5, 2, 3));
+/// assert!(!BuiltinFlipRule::MaxOfEnds.flips(5, 2, 3));
+/// assert!(BuiltinFlipRule::Product.flips(5, 2, 3));
+/// assert!(!BuiltinFlipRule::Difference.flips(5, 2, 3));
+/// assert!(BuiltinFlipRule::AlwaysFlip.flips(5, 2, 3));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BuiltinFlipRule {
+    /// Reversed if the value is less than the sum of both ends.
+    #[default]
+    StrictSum,
+    /// Reversed if the value is less than or equal to the sum of both ends.
+    InclusiveSum,
+    /// Reversed if the value is less than or equal to the larger of both ends.
+    MaxOfEnds,
+    /// Reversed if the value is less than or equal to the product of both ends.
+    Product,
+    /// Reversed if the value is less than or equal to the difference between both ends.
+    Difference,
+    /// Always reversed, as in classic Reversi.
+    AlwaysFlip,
+}
+impl BuiltinFlipRule {
+    /// Every built-in rule, in the order shown in the menu.
+    pub const ALL: [Self; 6] = [
+        Self::StrictSum,
+        Self::InclusiveSum,
+        Self::MaxOfEnds,
+        Self::Product,
+        Self::Difference,
+        Self::AlwaysFlip,
+    ];
+
+    /// Returns the name of the rule shown in the menu.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::StrictSum => "Sum (strict)",
+            Self::InclusiveSum => "Sum (inclusive)",
+            Self::MaxOfEnds => "Max of ends",
+            Self::Product => "Product",
+            Self::Difference => "Difference",
+            Self::AlwaysFlip => "Always flip",
+        }
+    }
+}
+impl FlipRule for BuiltinFlipRule {
+    fn flips(&self, sandwiched: u8, put: u8, opposite: u8) -> bool {
+        let sandwiched = sandwiched as u16;
+        let (put, opposite) = (put as u16, opposite as u16);
+        match self {
+            Self::StrictSum => sandwiched < put + opposite,
+            Self::InclusiveSum => sandwiched <= put + opposite,
+            Self::MaxOfEnds => sandwiched <= put.max(opposite),
+            Self::Product => sandwiched <= put * opposite,
+            Self::Difference => sandwiched <= put.abs_diff(opposite),
+            Self::AlwaysFlip => true,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod flip_rule_test {
+    use crate::game::rule::flip_rule::{BuiltinFlipRule, FlipRule};
+
+    #[test]
+    fn when_value_equals_the_sum_then_only_inclusive_sum_flips() {
+        assert!(!BuiltinFlipRule::StrictSum.flips(4, 1, 3));
+        assert!(BuiltinFlipRule::InclusiveSum.flips(4, 1, 3));
+    }
+
+    #[test]
+    fn when_ends_are_equal_then_difference_flips_nothing() {
+        assert!(!BuiltinFlipRule::Difference.flips(1, 4, 4));
+        assert!(BuiltinFlipRule::Difference.flips(3, 1, 4));
+    }
+
+    #[test]
+    fn when_values_are_large_then_product_does_not_overflow() {
+        assert!(BuiltinFlipRule::Product.flips(255, 255, 255));
+        assert!(BuiltinFlipRule::StrictSum.flips(255, 255, 1));
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::BTreeMap;
+
+use rand::Rng;
+
+use crate::game::{
+    assets::{bit_board::BitBoard, board::Board},
+    rule::{
+        position::{Position, ReversibleCandidates},
+        search::{
+            search_down,
+            search_down_left,
+            search_down_right,
+            search_left,
+            search_right,
+            search_up,
+            search_up_left,
+            search_up_right
+        },
+        turn::Turn
+    }
+};
+
+/// `LegalMoves` maps each puttable `Position` to the `ReversibleCandidates` of a piece put there.
+///
+/// It is derived from a `Board` and a `Turn` on demand, so the board itself only holds the pieces.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct LegalMoves {
+    pub moves: BTreeMap<Position, Vec<ReversibleCandidates>>,
+}
+impl LegalMoves {
+    /// Finds every puttable position on the `Board` for the `Turn`.
+    ///
+    /// On the 8x8 board the puttable squares are found at once with `BitBoard::legal_moves`,
+    /// and only those squares are searched in each direction for the `ReversibleCandidates`.
+    /// On the other sizes every empty square is searched.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use number_reversi::game::{
+    ///     assets::{board::Board, player::Player},
+    ///     rule::{turn::Turn, color::Color, legal_moves::LegalMoves, position::Position}
+    /// };
+    ///
+    /// let board = Board::set_initial_state();
+    /// let turn = Turn{color: Color::Black, player: Player::Human};
+    ///
+    /// let legal_moves = LegalMoves::new(&board, turn);
+    ///
+    /// assert_eq!(legal_moves.positions(), vec![
+    ///     Position{x: 2, y: 4}, Position{x: 3, y: 5}, Position{x: 4, y: 2}, Position{x: 5, y: 3}
+    /// ]);
+    /// ```
+    pub fn new(board: &Board, turn: Turn) -> Self {
+        let check_functions = [
+            search_down,
+            search_down_left,
+            search_down_right,
+            search_left,
+            search_right,
+            search_up,
+            search_up_left,
+            search_up_right,
+        ];
+        let positions = match BitBoard::try_from(board) {
+            Ok(bit_board) => BitBoard::positions(bit_board.legal_moves(turn.color)),
+            Err(_) => (0..board.size())
+                .flat_map(|x| (0..board.size()).map(move |y| Position{x, y}))
+                .filter(|position| !board.is_put(*position))
+                .collect(),
+        };
+        let moves = positions
+            .into_iter()
+            .map(|position| {
+                let candidates = check_functions.iter()
+                    .flat_map(|f| f(board, position, turn))
+                    .collect::<Vec<_>>();
+                (position, candidates)
+            })
+            .filter(|(_, candidates)| !candidates.is_empty())
+            .collect();
+        Self { moves }
+    }
+
+    /// Returns whether there is no puttable position.
+    pub fn is_empty(&self) -> bool {
+        self.moves.is_empty()
+    }
+
+    /// Returns whether a piece can be put on the `Position`.
+    pub fn contains(&self, position: Position) -> bool {
+        self.moves.contains_key(&position)
+    }
+
+    /// Returns the `ReversibleCandidates` of a piece put on the `Position`, if it is puttable.
+    pub fn get(&self, position: Position) -> Option<&Vec<ReversibleCandidates>> {
+        self.moves.get(&position)
+    }
+
+    /// Returns the puttable positions in ascending order.
+    pub fn positions(&self) -> Vec<Position> {
+        self.moves.keys().copied().collect()
+    }
+
+    /// Returns one of the puttable positions, chosen by the `rng`.
+    pub fn get_puttable_position(&self, rng: &mut impl Rng) -> Position {
+        let pos = self.positions();
+        let i = rng.gen_range(0..pos.len());
+        pos[i]
+    }
+}
+
+#[cfg(test)]
+mod legal_moves_test {
+    use crate::{
+        game::{
+            assets::{board::Board, piece::Piece, player::Player},
+            rule::{
+                color::Color,
+                legal_moves::LegalMoves,
+                position::{Position, ReversibleCandidates},
+                turn::Turn
+            }
+        },
+        tests::helper::board_helper::BoardHelper
+    };
+
+    #[test]
+    fn when_no_exist_reversible_piece_all_way_then_the_position_is_not_puttable() {
+        let board = Board::set_initial_state();
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let legal_moves = LegalMoves::new(&board, turn);
+        assert!(!legal_moves.contains(Position{x: 0, y: 0}));
+        assert_eq!(legal_moves.get(Position{x: 0, y: 0}), None);
+    }
+
+    #[test]
+    fn when_exist_reversible_piece_then_the_position_is_puttable() {
+        let board = Board::set_initial_state();
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        let legal_moves = LegalMoves::new(&board, turn);
+        assert_eq!(legal_moves.get(Position{x: 2, y: 4}),
+            Some(&vec![
+                ReversibleCandidates{
+                    opposite: Position { x: 4, y: 4 },
+                    positions: vec![Position { x: 3, y: 4 }],
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn when_exist_reversible_piece_then_the_position_is_puttable2() {
+        let board = Board::set_initial_state();
+        let white = Color::White;
+        let black = Color::Black;
+        let turn = Turn{color: black, player: Player::Human};
+        let board = board.put_piece(Position { x: 3, y: 5 }, Piece::new(white, 1));
+        let board = board.put_piece(Position { x: 4, y: 5 }, Piece::new(white, 1));
+        let board = board.put_piece(Position { x: 5, y: 4 }, Piece::new(black, 3));
+        let legal_moves = LegalMoves::new(&board, turn);
+        assert_eq!(legal_moves.get(Position{x: 3, y: 6}),
+            Some(&vec![
+                ReversibleCandidates{
+                    opposite: Position { x: 3, y: 3 },
+                    positions: vec![Position { x: 3, y: 5 }, Position { x: 3, y: 4 }],
+                },
+                ReversibleCandidates{
+                    opposite: Position { x: 5, y: 4 },
+                    positions: vec![Position { x: 4, y: 5 }],
+                }]
+            )
+        );
+    }
+
+    #[test]
+    fn when_board_is_6x6_then_legal_moves_are_around_the_center() {
+        let board = Board::with_size(6);
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        assert_eq!(LegalMoves::new(&board, turn).positions(), vec![
+            Position{x: 1, y: 3}, Position{x: 2, y: 4}, Position{x: 3, y: 1}, Position{x: 4, y: 2}
+        ]);
+    }
+
+    #[test]
+    fn when_line_reaches_the_edge_of_12x12_board_then_it_is_searched() {
+        let board = Board::with_size(12)
+            .put_piece(Position{x: 11, y: 11}, Piece::new(Color::Black, 1))
+            .put_piece(Position{x: 10, y: 11}, Piece::new(Color::White, 1));
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        assert_eq!(LegalMoves::new(&board, turn).get(Position{x: 9, y: 11}), Some(&vec![
+            ReversibleCandidates::new(Position{x: 11, y: 11}, vec![Position{x: 10, y: 11}])
+        ]));
+    }
+
+    #[test]
+    fn when_there_is_no_own_piece_then_legal_moves_are_empty() {
+        let board = BoardHelper::make_board(vec![], vec![(3, 3, 1), (4, 4, 1)]);
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        assert!(LegalMoves::new(&board, turn).is_empty());
+    }
+
+    #[test]
+    fn when_diagram_marks_puttable_squares_then_they_are_the_legal_moves() {
+        let (board, mut marked) = Board::parse_diagram("
+                a   b   c   d   e   f
+            1   .   .   .   .   .   .
+            2   .   .   .   .   .   .
+            3   .   .  b3  w7   *   .
+            4   .   .  w2 w10   .   .
+            5   .   .   *   .   *   .
+            6   .   .   .   .   .   .
+        ").unwrap();
+        marked.sort();
+        let turn = Turn{color: Color::Black, player: Player::Human};
+        assert_eq!(LegalMoves::new(&board, turn).positions(), marked, "\n{}", board);
+    }
+}
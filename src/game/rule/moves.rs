@@ -0,0 +1,177 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::game::assets::piece::Piece;
+
+use super::{color::Color, position::{ParsePositionError, Position}};
+
+/// Represents a single action a player can take on their turn.
+///
+/// A `Move::Put` is written as the color (`B` or `W`), the value of the piece and the square,
+/// such as `B7e3`, and a `Move::Pass` is written as `pass`.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::assets::piece::Piece;
+/// use number_reversi::game::rule::{color::Color, moves::Move, position::Position};
+///
+/// let m: Move = "B7e3".parse().unwrap();
+/// assert_eq!(m, Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 7)));
+/// assert_eq!(m.to_string(), "B7e3");
+/// assert_eq!("pass".parse::<Move>(), Ok(Move::Pass));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Move {
+    /// Put the `Piece` on the `Position`.
+    Put {
+        position: Position,
+        piece: Piece,
+    },
+    /// Hand the turn over to the opponent without putting a piece.
+    Pass,
+}
+impl Move {
+    /// Creates a new `Move::Put`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::piece::Piece;
+    /// use number_reversi::game::rule::{color::Color, moves::Move, position::Position};
+    ///
+    /// let m = Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 7));
+    /// assert_eq!(m, Move::Put{position: Position{x: 4, y: 2}, piece: Piece::new(Color::Black, 7)});
+    /// ```
+    pub fn put(position: Position, piece: Piece) -> Self {
+        Move::Put { position, piece }
+    }
+}
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Move::Put { position, piece } => {
+                let color = match piece.color {
+                    Color::Black => 'B',
+                    Color::White => 'W',
+                };
+                write!(f, "{}{}{}", color, piece.value, position)
+            },
+            Move::Pass => write!(f, "pass"),
+        }
+    }
+}
+impl FromStr for Move {
+    type Err = ParseMoveError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("pass") {
+            return Ok(Move::Pass);
+        }
+        let color = match s.chars().next() {
+            Some('B' | 'b') => Color::Black,
+            Some('W' | 'w') => Color::White,
+            _ => return Err(ParseMoveError::InvalidColor(s.to_string())),
+        };
+        let rest = &s[1..];
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let value = rest[..digits].parse::<u8>()
+            .map_err(|_| ParseMoveError::InvalidValue(s.to_string()))?;
+        let position = rest[digits..].parse::<Position>().map_err(ParseMoveError::InvalidPosition)?;
+        Ok(Move::put(position, Piece::new(color, value)))
+    }
+}
+
+/// The error returned when a string is not a `Move` in the notation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseMoveError {
+    /// The string does not start with `B` or `W`, and is not `pass`.
+    InvalidColor(String),
+    /// The color is not followed by the value of a piece.
+    InvalidValue(String),
+    /// The value is not followed by a square.
+    InvalidPosition(ParsePositionError),
+}
+impl fmt::Display for ParseMoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseMoveError::InvalidColor(s) => write!(f, "'{}' does not start with B or W", s),
+            ParseMoveError::InvalidValue(s) => write!(f, "'{}' does not have the value of a piece", s),
+            ParseMoveError::InvalidPosition(e) => write!(f, "{}", e),
+        }
+    }
+}
+impl Error for ParseMoveError {}
+
+
+/// Represents the reason why a `Move` was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveError {
+    /// The `Position` is outside of the board.
+    OutOfBounds(Position),
+    /// The `Square` at the `Position` is not puttable for the current turn.
+    SquareNotPuttable(Position),
+    /// The player has no piece of the value of the `Piece` left.
+    NoPiecesOfValue(Piece),
+    /// The `Piece` does not belong to the player of the current turn.
+    WrongTurn(Color),
+    /// The player cannot pass because they can put a piece.
+    PassNotAllowed,
+    /// The game is already over.
+    GameOver,
+}
+impl fmt::Display for MoveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MoveError::OutOfBounds(p) => write!(f, "({}, {}) is out of the board", p.x, p.y),
+            MoveError::SquareNotPuttable(p) => write!(f, "cannot put a piece on {}", p),
+            MoveError::NoPiecesOfValue(p) => write!(f, "no {:?} piece of value {} is left", p.color, p.value),
+            MoveError::WrongTurn(c) => write!(f, "it is not {:?}'s turn", c),
+            MoveError::PassNotAllowed => write!(f, "cannot pass while a piece can be put"),
+            MoveError::GameOver => write!(f, "the game is already over"),
+        }
+    }
+}
+impl Error for MoveError {}
+
+
+#[cfg(test)]
+mod moves_test {
+    use crate::game::{
+        assets::piece::Piece,
+        rule::{color::Color, moves::{Move, ParseMoveError}, position::{ParsePositionError, Position}}
+    };
+
+    #[test]
+    fn when_move_is_written_then_it_is_parsed_to_the_same_move() {
+        let moves = [
+            Move::put(Position{x: 0, y: 0}, Piece::new(Color::Black, 1)),
+            Move::put(Position{x: 7, y: 7}, Piece::new(Color::White, 10)),
+            Move::put(Position{x: 11, y: 9}, Piece::new(Color::White, 12)),
+            Move::Pass,
+        ];
+        for m in moves {
+            assert_eq!(m.to_string().parse::<Move>(), Ok(m));
+        }
+        assert_eq!(Move::put(Position{x: 7, y: 7}, Piece::new(Color::White, 10)).to_string(), "W10h8");
+    }
+
+    #[test]
+    fn when_case_differs_then_it_is_still_parsed() {
+        assert_eq!("b7E3".parse::<Move>(), Ok(Move::put(Position{x: 4, y: 2}, Piece::new(Color::Black, 7))));
+        assert_eq!("PASS".parse::<Move>(), Ok(Move::Pass));
+    }
+
+    #[test]
+    fn when_string_is_not_a_move_then_returns_error() {
+        assert_eq!("X7e3".parse::<Move>(), Err(ParseMoveError::InvalidColor("X7e3".to_string())));
+        assert_eq!("".parse::<Move>(), Err(ParseMoveError::InvalidColor("".to_string())));
+        assert_eq!("Be3".parse::<Move>(), Err(ParseMoveError::InvalidValue("Be3".to_string())));
+        assert_eq!("B300e3".parse::<Move>(), Err(ParseMoveError::InvalidValue("B300e3".to_string())));
+        assert_eq!("B7".parse::<Move>(),
+            Err(ParseMoveError::InvalidPosition(ParsePositionError("".to_string()))));
+        assert_eq!("B7e0".parse::<Move>(),
+            Err(ParseMoveError::InvalidPosition(ParsePositionError("e0".to_string()))));
+    }
+}
@@ -1,8 +1,26 @@
+use std::{error::Error, fmt, str::FromStr};
+
+use serde::{Deserialize, Serialize};
 use yew::Properties;
 
 /// Position type is a struct that contains x and y coordinates
 ///  of a position on the board. 
-#[derive(Debug, Clone, Copy, PartialEq, Properties, Hash)]
+/// 
+/// A `Position` is written in algebraic notation: the column as a letter from `a`
+/// and the row as a number from `1`, so `a1` is the top left square and `h8` is
+/// the bottom right square of the 8x8 board.
+/// 
+/// # Example
+/// 
+/// ```
+/// use number_reversi::game::rule::position::Position;
+/// 
+/// let position: Position = "e3".parse().unwrap();
+/// assert_eq!(position, Position { x: 4, y: 2 });
+/// assert_eq!(position.to_string(), "e3");
+/// assert!("z0".parse::<Position>().is_err());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Properties, Hash, Serialize, Deserialize)]
 pub struct Position {
     pub x: usize,
     pub y: usize,
@@ -17,7 +35,7 @@ impl Position {
     /// # Example
     /// 
     /// ```
-    /// use number-reversi::game::rule::position::Position;
+    /// use number_reversi::game::rule::position::Position;
     /// 
     /// let position = Position { x: 3, y: 4 };
     /// let next_position = position.next_position((1, 1));
@@ -28,6 +46,45 @@ impl Position {
         (self.x as isize + d.0, self.y as isize + d.1)
     }
 }
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match u8::try_from(self.x).ok().filter(|x| *x < COLUMNS) {
+            Some(x) => write!(f, "{}{}", (b'a' + x) as char, self.y + 1),
+            None => write!(f, "({}, {})", self.x, self.y),
+        }
+    }
+}
+impl FromStr for Position {
+    type Err = ParsePositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParsePositionError(s.to_string());
+        let mut chars = s.chars();
+        let column = chars.next().filter(|c| c.is_ascii_alphabetic()).ok_or_else(error)?;
+        let row = chars.as_str();
+        if row.is_empty() || !row.chars().all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+        let row = row.parse::<usize>().ok().filter(|row| *row > 0).ok_or_else(error)?;
+        Ok(Self {
+            x: (column.to_ascii_lowercase() as u8 - b'a') as usize,
+            y: row - 1,
+        })
+    }
+}
+
+/// The number of columns that can be written as a letter.
+const COLUMNS: u8 = 26;
+
+/// The error returned when a string is not a square in algebraic notation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParsePositionError(pub String);
+impl fmt::Display for ParsePositionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' is not a square such as a1 or h8", self.0)
+    }
+}
+impl Error for ParsePositionError {}
 
 
 #[derive(Debug, PartialEq, Clone)]
@@ -46,7 +103,7 @@ impl ReversibleCandidates {
     /// # Example
     /// 
     /// ```
-    /// use number-reversi::game::rule::position::{Position, ReversibleCandidates};
+    /// use number_reversi::game::rule::position::{Position, ReversibleCandidates};
     /// 
     /// let opposite = Position { x: 0, y: 0 };
     /// let positions = vec![
@@ -57,4 +114,35 @@ impl ReversibleCandidates {
     pub fn new(opposite: Position, positions: Vec<Position>) -> Self {
         Self { opposite, positions }
     }
-}
\ No newline at end of file
+}
+
+
+#[cfg(test)]
+mod position_test {
+    use crate::game::rule::position::{ParsePositionError, Position};
+
+    #[test]
+    fn when_corners_are_written_then_they_are_a1_and_h8() {
+        assert_eq!(Position{x: 0, y: 0}.to_string(), "a1");
+        assert_eq!(Position{x: 7, y: 7}.to_string(), "h8");
+        assert_eq!(Position{x: 11, y: 11}.to_string(), "l12");
+    }
+
+    #[test]
+    fn when_square_is_parsed_then_it_is_the_same_position() {
+        for x in 0..12 {
+            for y in 0..12 {
+                let position = Position{x, y};
+                assert_eq!(position.to_string().parse::<Position>(), Ok(position));
+            }
+        }
+        assert_eq!("H8".parse::<Position>(), Ok(Position{x: 7, y: 7}));
+    }
+
+    #[test]
+    fn when_string_is_not_a_square_then_returns_error() {
+        for s in ["", "e", "3", "e0", "e-1", "e3x", "é3", "ee3"] {
+            assert_eq!(s.parse::<Position>(), Err(ParsePositionError(s.to_string())));
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use super::{color::Color, score::Score};
+
+/// Represents why a game has ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndReason {
+    /// Every square of the board has a piece.
+    BoardFull,
+    /// Neither player can put a piece.
+    NoMoves,
+    /// The player of the `Color` has no pieces left on their turn.
+    PiecesExhausted(Color),
+}
+
+/// Represents the final result of a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GameResult {
+    /// The color with the higher score, or `None` for a draw.
+    pub winner: Option<Color>,
+    pub black_score: usize,
+    pub white_score: usize,
+    pub reason: EndReason,
+}
+impl GameResult {
+    /// Creates a new `GameResult` from the final `Score`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::rule::{
+    ///     color::Color,
+    ///     result::{EndReason, GameResult},
+    ///     score::Score
+    /// };
+    ///
+    /// let result = GameResult::new(Score{black: 30, white: 42}, EndReason::BoardFull);
+    /// assert_eq!(result.winner, Some(Color::White));
+    ///
+    /// let result = GameResult::new(Score{black: 8, white: 8}, EndReason::NoMoves);
+    /// assert_eq!(result.winner, None);
+    /// ```
+    pub fn new(score: Score, reason: EndReason) -> Self {
+        let winner = match score.black.cmp(&score.white) {
+            std::cmp::Ordering::Greater => Some(Color::Black),
+            std::cmp::Ordering::Less => Some(Color::White),
+            std::cmp::Ordering::Equal => None,
+        };
+        Self {
+            winner,
+            black_score: score.black,
+            white_score: score.white,
+            reason,
+        }
+    }
+}
@@ -0,0 +1,40 @@
+use serde::{Deserialize, Serialize};
+
+use crate::game::assets::{board::DEFAULT_SIZE, piece_set::PieceSet};
+
+use super::flip_rule::BuiltinFlipRule;
+
+/// `Rules` holds the settings of a game that are chosen before it starts.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::assets::piece_set::PieceSet;
+/// use number_reversi::game::rule::rules::Rules;
+///
+/// let rules = Rules { board_size: 10, ..Rules::default() };
+/// assert_eq!(rules.board_size, 10);
+/// assert_eq!(Rules::default().board_size, 8);
+/// assert_eq!(Rules::default().piece_set, PieceSet::standard());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Rules {
+    /// The number of squares on each side of the board.
+    pub board_size: usize,
+    /// The pieces each player has at the start of the game.
+    pub piece_set: PieceSet,
+    /// The rule deciding which sandwiched pieces are reversed.
+    pub flip_rule: BuiltinFlipRule,
+    /// Whether moves can be taken back. Rated games against COM disable it.
+    pub takebacks: bool,
+}
+impl Default for Rules {
+    fn default() -> Self {
+        Self {
+            board_size: DEFAULT_SIZE,
+            piece_set: PieceSet::default(),
+            flip_rule: BuiltinFlipRule::default(),
+            takebacks: true,
+        }
+    }
+}
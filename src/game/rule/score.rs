@@ -1,7 +1,8 @@
+use serde::{Deserialize, Serialize};
 use yew::Properties;
 
 /// Score type represents the total number of pieces on the Board for each color
-#[derive(Debug, Properties, Clone, PartialEq, Copy)]
+#[derive(Debug, Properties, Clone, PartialEq, Copy, Serialize, Deserialize)]
 pub struct Score {
     pub black: usize,
     pub white: usize,
@@ -20,19 +20,15 @@ use crate::game::{
 /// # Returns
 /// A vector of `Position`s of the pieces that are able to be reversed.
 pub fn search(board: &Board, position: Position, turn: Turn, direction: (isize, isize), result: Option<Vec<Position>>) -> Vec<Position> {
-    let mut result = match result {
-        Some(r) => r,
-        None => vec![],
-    };
+    let mut result = result.unwrap_or_default();
     
-    if board.is_put(position) {
-        if result.len() == 0 {
-            return vec![];
-        }
+    if board.is_put(position) && result.is_empty() {
+        return vec![];
     };
     
     let next = position.next_position(direction);
-    if next.0 < 0 || next.0 >= 8 || next.1 < 0 || next.1 >= 8 {
+    let size = board.size() as isize;
+    if next.0 < 0 || next.0 >= size || next.1 < 0 || next.1 >= size {
         return vec![];
     }
 
@@ -40,7 +36,7 @@ pub fn search(board: &Board, position: Position, turn: Turn, direction: (isize,
     match board.squares[next.x][next.y] {
         Square::Put(p) => {
             if p.color == turn.color {
-                if result.len() == 0 {
+                if result.is_empty() {
                     vec![]
                 } else {
                     result.push(next);
@@ -48,7 +44,7 @@ pub fn search(board: &Board, position: Position, turn: Turn, direction: (isize,
                 }
             } else {
                 result.push(next);
-                search(board, next.clone(), turn, direction, Some(result))
+                search(board, next, turn, direction, Some(result))
             }
         },
         _ => vec![]
@@ -57,66 +53,42 @@ pub fn search(board: &Board, position: Position, turn: Turn, direction: (isize,
 pub fn search_up(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (0, -1), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_up_right(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (1, -1), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_right(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (1, 0), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_down_right(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (1, 1), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_down(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (0, 1), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_down_left(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (-1, 1), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_left(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (-1, 0), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 pub fn search_up_left(board: &Board, position: Position, turn: Turn) -> Option<ReversibleCandidates> {
     let mut positions = search(board, position, turn, (-1, -1), None);
     let opposite = positions.pop();
-    match opposite {
-        Some(p) => Some(ReversibleCandidates::new(p, positions)),
-        None => None,
-    }
+    opposite.map(|p| ReversibleCandidates::new(p, positions))
 }
 
 #[cfg(test)]
@@ -0,0 +1,121 @@
+use crate::game::assets::board::Board;
+
+use super::{moves::Move, position::Position};
+
+/// `Symmetry` is one of the 8 ways to turn or flip a square board onto itself.
+/// A game is played the same on a turned or flipped board, so the positions which
+/// are the same but for a `Symmetry` can share what is known about them.
+///
+/// # Examples
+///
+/// ```
+/// use number_reversi::game::rule::{position::Position, symmetry::Symmetry};
+///
+/// let position = Position{x: 1, y: 0};
+/// assert_eq!(Symmetry::Rotate90.apply(position, 8), Position{x: 7, y: 1});
+/// assert_eq!(Symmetry::Rotate90.inverse().apply(Position{x: 7, y: 1}, 8), position);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Symmetry {
+    Identity,
+    /// Turns the board a quarter clockwise.
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Flips the board left and right.
+    FlipX,
+    /// Flips the board upside down.
+    FlipY,
+    /// Flips the board over the diagonal from a1.
+    Transpose,
+    /// Flips the board over the other diagonal.
+    AntiTranspose,
+}
+impl Symmetry {
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::Identity, Symmetry::Rotate90, Symmetry::Rotate180, Symmetry::Rotate270,
+        Symmetry::FlipX, Symmetry::FlipY, Symmetry::Transpose, Symmetry::AntiTranspose,
+    ];
+
+    /// Returns where the `Position` goes on a board of the size.
+    pub fn apply(self, position: Position, size: usize) -> Position {
+        let (x, y, last) = (position.x, position.y, size - 1);
+        let (x, y) = match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::Rotate90 => (last - y, x),
+            Symmetry::Rotate180 => (last - x, last - y),
+            Symmetry::Rotate270 => (y, last - x),
+            Symmetry::FlipX => (last - x, y),
+            Symmetry::FlipY => (x, last - y),
+            Symmetry::Transpose => (y, x),
+            Symmetry::AntiTranspose => (last - y, last - x),
+        };
+        Position{x, y}
+    }
+
+    /// Returns the `Symmetry` which takes every position back.
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::Rotate90 => Symmetry::Rotate270,
+            Symmetry::Rotate270 => Symmetry::Rotate90,
+            symmetry => symmetry,
+        }
+    }
+
+    /// Returns the `Board` with every square moved by the `Symmetry`.
+    pub fn board(self, board: &Board) -> Board {
+        let size = board.size();
+        let mut squares = board.squares.clone();
+        for (x, column) in board.squares.iter().enumerate() {
+            for (y, square) in column.iter().enumerate() {
+                let position = self.apply(Position{x, y}, size);
+                squares[position.x][position.y] = square.clone();
+            }
+        }
+        Board { squares }
+    }
+
+    /// Returns the `Move` put on the square moved by the `Symmetry`.
+    pub fn play(self, m: Move, size: usize) -> Move {
+        match m {
+            Move::Put { position, piece } => Move::put(self.apply(position, size), piece),
+            Move::Pass => Move::Pass,
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod symmetry_test {
+    use crate::game::{assets::board::Board, rule::{position::Position, symmetry::Symmetry}};
+
+    #[test]
+    fn when_symmetry_and_its_inverse_are_applied_then_every_position_comes_back() {
+        for symmetry in Symmetry::ALL {
+            for (x, y) in [(0, 0), (1, 0), (2, 3), (5, 1)] {
+                let position = Position{x, y};
+                assert_eq!(symmetry.inverse().apply(symmetry.apply(position, 6), 6), position, "{:?}", symmetry);
+            }
+        }
+        let moved = Symmetry::ALL.map(|symmetry| symmetry.apply(Position{x: 1, y: 0}, 6));
+        assert!(moved.iter().enumerate().all(|(i, a)| moved[i + 1..].iter().all(|b| a != b)));
+    }
+
+    #[test]
+    fn when_board_is_moved_then_pieces_follow_the_positions() {
+        let board: Board = "
+            b3  .  .  .
+             .  .  .  .
+             .  . w2  .
+             .  .  .  .
+        ".parse().unwrap();
+        let turned: Board = "
+             .  .  . b3
+             .  .  .  .
+             . w2  .  .
+             .  .  .  .
+        ".parse().unwrap();
+        assert_eq!(Symmetry::Rotate90.board(&board), turned);
+        assert_eq!(Symmetry::Rotate90.inverse().board(&turned), board);
+    }
+}
@@ -1,21 +1,32 @@
+use serde::{Deserialize, Serialize};
 use yew::Properties;
 
-use crate::{app::game::GameModeProps, game::assets::player::Player};
+use crate::game::assets::player::{Player, Players};
 
 use super::color::Color;
 
 /// A struct that represents the turn of play in a game.
-#[derive(Debug, Clone, Copy, Properties, PartialEq)]
+#[derive(Debug, Clone, Copy, Properties, PartialEq, Serialize, Deserialize)]
 pub struct Turn{
     pub color: Color,
     pub player: Player,
 }
 
 impl Turn {
-    pub fn change(&self, game_mode_prop: GameModeProps) -> Self {
-        match &self.color {
-            Color::Black => Turn{color: Color::White, player: game_mode_prop.white},
-            Color::White => Turn{color: Color::Black, player: game_mode_prop.black},
-        }
+    /// Returns the turn of the opponent of the current turn.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use number_reversi::game::assets::player::{Player, Players};
+    /// use number_reversi::game::rule::{color::Color, turn::Turn};
+    ///
+    /// let players = Players::new(Player::Human, Player::RANDOM_COM);
+    /// let turn = Turn{color: Color::Black, player: Player::Human};
+    /// assert_eq!(turn.change(players), Turn{color: Color::White, player: Player::RANDOM_COM});
+    /// ```
+    pub fn change(&self, players: Players) -> Self {
+        let color = self.color.opponent();
+        Turn{color, player: players.get(color)}
     }
-}
\ No newline at end of file
+}
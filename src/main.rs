@@ -1,12 +1,6 @@
 use yew::prelude::*;
-use views::page::header::Header;
-use app::menu::Menu;
-
-mod controller;
-mod game;
-mod views;
-mod tests;
-mod app;
+use number_reversi::views::page::header::Header;
+use number_reversi::app::menu::Menu;
 
 #[function_component]
 fn App() -> Html {
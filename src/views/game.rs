@@ -2,4 +2,9 @@ pub mod piece_view;
 pub mod board_view;
 pub mod decrement_button;
 pub mod increment_button;
-pub mod score_view;
\ No newline at end of file
+pub mod undo_button;
+pub mod redo_button;
+pub mod score_view;
+pub mod result_view;
+pub mod record_view;
+pub mod com_controls;
\ No newline at end of file
@@ -1,40 +1,44 @@
+use std::collections::BTreeMap;
 
 use yew::prelude::*;
 use yew::virtual_dom::VNode;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
-use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen::JsCast;
 
 use crate::game::{
-    assets::{
-        board::Board, 
-        square::Square
-    }, 
-    rule::{color::Color, position::Position}
+    assets::square::Square, 
+    rule::{color::Color, legal_moves::LegalMoves, position::Position}
 };
 
 #[derive(Debug, Properties, Clone, PartialEq)]
 pub struct BoardViewProps {
     pub squares: Vec<Vec<Square>>,
+    pub legal_moves: LegalMoves,
+    /// The pieces reversed by the selected piece, for each puttable position.
+    /// They are marked while the pointer is over the position.
+    #[prop_or_default]
+    pub previews: BTreeMap<Position, Vec<Position>>,
     pub on_put: Callback<Position>,
 }
 
+/// The length of a side of a square on the canvas, in pixels.
+const SQUARE_SIZE: f64 = 50.0;
+
 pub struct BoardView {
-    board: BoardViewProps,
     canvas: Option<HtmlCanvasElement>,
     context: Option<CanvasRenderingContext2d>,
+    hovered: Option<Position>,
 }
 
 impl Component for BoardView {
-    type Message = ();
+    type Message = Option<Position>;
     type Properties = BoardViewProps;
 
-    fn create(ctx: &Context<Self>) -> Self {
-        let board = ctx.props().clone();
-        // let on_click = ctx.props().on_put.clone;
+    fn create(_ctx: &Context<Self>) -> Self {
         Self {
             canvas: None,
             context: None,
-            board,
+            hovered: None,
         }
     }
 
@@ -48,12 +52,16 @@ impl Component for BoardView {
         
         let context = self.context.as_ref().unwrap();
 
+        let size = ctx.props().squares.len();
+        let square_size = SQUARE_SIZE;
+
+        let previews = self.hovered.and_then(|p| ctx.props().previews.get(&p));
+
         context.begin_path();
-        context.clear_rect(0.0, 0.0, 400.0, 400.0);
-        let square_size = 50.0;
+        context.clear_rect(0.0, 0.0, size as f64 * square_size, size as f64 * square_size);
         
-        for i in 0..8 {
-            for j in 0..8 {
+        for i in 0..size {
+            for j in 0..size {
                 if (i + j) % 2 == 0 {
                     draw_square(context, i, square_size, j, "#228B22");
                 } else {
@@ -72,22 +80,30 @@ impl Component for BoardView {
                                 draw_piece(context, x, y, square_size, "white", piece.value, "black");
                             },
                         };
+                        if previews.is_some_and(|p| p.contains(&Position{x: i, y: j})) {
+                            draw_preview(context, x, y, square_size);
+                        }
                     },
-                    Square::Puttable(_) => {
+                    Square::Empty if ctx.props().legal_moves.contains(Position{x: i, y: j}) => {
                         draw_square(context, i, square_size, j, "#FFFF90");
                     },
-                    _ => (),
+                    Square::Empty => (),
                 }
             }
         }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        if self.hovered == msg {
+            return false;
+        }
+        self.hovered = msg;
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> VNode {
-        let square_size = 50.0;
+        let square_size = SQUARE_SIZE;
+        let canvas_size = (ctx.props().squares.len() as f64 * square_size).to_string();
         let on_click = {
             let on_put = ctx.props().on_put.clone();
             Callback::from(move |e: MouseEvent| {
@@ -101,16 +117,30 @@ impl Component for BoardView {
             })
         };
 
+        let on_mouse_move = ctx.link().callback(move |e: MouseEvent| {
+            let x = (e.offset_x() as f64 / square_size).floor() as usize;
+            let y = (e.offset_y() as f64 / square_size).floor() as usize;
+            Some(Position{x, y})
+        });
+        let on_mouse_leave = ctx.link().callback(|_: MouseEvent| None);
+
         html! {
             <div>
-                <canvas id="aboard" width="400" height="400" onclick={on_click} ></canvas>
+                <canvas 
+                    id="aboard" 
+                    width={canvas_size.clone()} 
+                    height={canvas_size} 
+                    onclick={on_click} 
+                    onmousemove={on_mouse_move} 
+                    onmouseleave={on_mouse_leave}
+                ></canvas>
             </div>
         }
     }
 }
 
 fn draw_square(context: &CanvasRenderingContext2d, i: usize, square_size: f64, j: usize, color: &str) {
-    context.set_fill_style(&JsValue::from_str(color));
+    context.set_fill_style_str(color);
     context.fill_rect(i as f64 * square_size, j as f64 * square_size, square_size, square_size);
     context.rect(i as f64 * square_size, j as f64 * square_size, square_size, square_size);
     context.stroke();
@@ -118,13 +148,23 @@ fn draw_square(context: &CanvasRenderingContext2d, i: usize, square_size: f64, j
 
 fn draw_piece(context: &CanvasRenderingContext2d, x: f64, y: f64, square_size: f64, piece_color: &str, value: u8, font_color: &str) {
     context.begin_path();
-    context.set_fill_style(&JsValue::from_str(piece_color));
+    context.set_fill_style_str(piece_color);
     context.arc(x, y, square_size / 2.0 - 5.0, 0.0, 2.0 * std::f64::consts::PI).unwrap();
     context.fill();
     context.stroke();
-    context.set_fill_style(&JsValue::from_str(font_color));
+    context.set_fill_style_str(font_color);
     context.set_text_align("center");
     context.set_text_baseline("middle");
-    context.set_font(&format!("bold 25px serif"));
-    context.fill_text(&value.to_string(), x, y);
+    context.set_font("bold 25px serif");
+    context.fill_text(&value.to_string(), x, y).unwrap();
+}
+
+fn draw_preview(context: &CanvasRenderingContext2d, x: f64, y: f64, square_size: f64) {
+    context.begin_path();
+    context.set_stroke_style_str("red");
+    context.set_line_width(3.0);
+    context.arc(x, y, square_size / 2.0 - 5.0, 0.0, 2.0 * std::f64::consts::PI).unwrap();
+    context.stroke();
+    context.set_stroke_style_str("black");
+    context.set_line_width(1.0);
 }
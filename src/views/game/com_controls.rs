@@ -0,0 +1,78 @@
+use yew::prelude::*;
+use crate::controller::com::thinking::Progress;
+use yew::{Callback, function_component, html, Html, Properties};
+
+/// The delays before a COM move which can be chosen, in milliseconds, with their names.
+pub const DELAYS: [(&str, u32); 4] = [("Instant", 0), ("Fast", 200), ("Normal", 700), ("Slow", 1500)];
+/// The delay chosen when a game starts.
+pub const DEFAULT_DELAY: u32 = 700;
+
+#[derive(Properties, PartialEq)]
+pub struct ComControlsProps {
+    pub paused: bool,
+    /// The delay before a COM move in milliseconds.
+    pub delay: u32,
+    /// Whether it is the turn of COM, so that a move can be stepped.
+    pub com_turn: bool,
+    /// The progress of the search of COM, or `None` if COM is not thinking.
+    #[prop_or_default]
+    pub thinking: Option<Progress>,
+    pub on_pause: Callback<bool>,
+    pub on_step: Callback<()>,
+    pub on_delay: Callback<u32>,
+}
+
+#[function_component(ComControls)]
+pub fn com_controls(props: &ComControlsProps) -> Html {
+    let toggle_pause = {
+        let on_pause = props.on_pause.clone();
+        let paused = props.paused;
+
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_pause.emit(!paused)
+        })
+    };
+
+    let step = {
+        let on_step = props.on_step.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_step.emit(())
+        })
+    };
+
+    let delays = DELAYS.iter().map(|(name, delay)| {
+        let delay = *delay;
+        let select_delay = {
+            let on_delay = props.on_delay.clone();
+            Callback::from(move |_: MouseEvent| on_delay.emit(delay))
+        };
+        let id = format!("delay{}", delay);
+        html! {
+            <>
+                <input 
+                    type="radio" 
+                    id={id.clone()} 
+                    name="delay" 
+                    value={delay.to_string()} 
+                    checked={props.delay == delay}
+                    onclick={select_delay}
+                />
+                <label for={id}>{*name}</label>
+            </>
+        }
+    }).collect::<Html>();
+
+    html! {
+        <div class="d-flex">
+            <button type="submit" onclick={toggle_pause}>{if props.paused { "Resume" } else { "Pause" }}</button>
+            <button type="submit" disabled={!props.paused || !props.com_turn || props.thinking.is_some()} onclick={step}>{"Step"}</button>
+            <span>{delays}</span>
+            if let Some(progress) = props.thinking {
+                <span>{format!("Thinking… {}/{}", progress.done, progress.total)}</span>
+            }
+        </div>
+    }
+}
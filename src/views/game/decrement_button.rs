@@ -6,6 +6,8 @@ use crate::game::assets::piece::Piece;
 #[derive(Properties, PartialEq)]
 pub struct DecrementProps {
     pub piece: Piece,
+    pub min_value: u8,
+    pub max_value: u8,
     pub on_decrement: Callback<Piece>
 }
 
@@ -13,13 +15,15 @@ pub struct DecrementProps {
 pub fn decrement_button(props: &DecrementProps) -> Html {
     let decrement = {
         let on_decrement = props.on_decrement.clone();
-        let selected = props.piece.clone();
+        let selected = props.piece;
+        let (min_value, max_value) = (props.min_value, props.max_value);
 
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
-            let current_piece = match selected.value {
-                1 => Piece::new(selected.color, 10),
-                _ => Piece::new(selected.color, selected.value - 1)
+            let current_piece = if selected.value <= min_value {
+                Piece::new(selected.color, max_value)
+            } else {
+                Piece::new(selected.color, selected.value - 1)
             };
             on_decrement.emit(current_piece)
         })
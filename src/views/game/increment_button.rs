@@ -6,6 +6,8 @@ use crate::game::assets::piece::Piece;
 #[derive(Properties, PartialEq)]
 pub struct IncrementProps {
     pub piece: Piece,
+    pub min_value: u8,
+    pub max_value: u8,
     pub on_increment: Callback<Piece>
 }
 
@@ -13,13 +15,15 @@ pub struct IncrementProps {
 pub fn increment_button(props: &IncrementProps) -> Html {
     let increment = {
         let on_decrement = props.on_increment.clone();
-        let selected = props.piece.clone();
+        let selected = props.piece;
+        let (min_value, max_value) = (props.min_value, props.max_value);
 
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
-            let current_piece = match selected.value {
-                10 => Piece::new(selected.color, 1),
-                _ => Piece::new(selected.color, selected.value + 1)
+            let current_piece = if selected.value >= max_value {
+                Piece::new(selected.color, min_value)
+            } else {
+                Piece::new(selected.color, selected.value + 1)
             };
             on_decrement.emit(current_piece)
         })
@@ -2,7 +2,7 @@
 use yew::prelude::*;
 use yew::virtual_dom::VNode;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
-use wasm_bindgen::{prelude::*, JsCast};
+use wasm_bindgen::JsCast;
 
 use crate::game::rule::color::Color;
 
@@ -17,19 +17,16 @@ pub struct PieceViewProps {
 pub struct PieceView {
     canvas: Option<HtmlCanvasElement>,
     context: Option<CanvasRenderingContext2d>,
-    piece_props: PieceViewProps,
 }
 
 impl Component for PieceView {
     type Message = ();
     type Properties = PieceViewProps;
 
-    fn create(ctx: &Context<Self>) -> Self {
-        let piece = ctx.props();
+    fn create(_ctx: &Context<Self>) -> Self {
         Self {
             canvas: None,
             context: None,
-            piece_props: *piece,
         }
     }
 
@@ -50,10 +47,10 @@ impl Component for PieceView {
         context.clear_rect(x, y, 50.0, 50.0);
         match ctx.props().color {
             Color::Black => {
-                context.set_fill_style(&JsValue::from_str("black"));
+                context.set_fill_style_str("black");
             },
             Color::White => {
-                context.set_fill_style(&JsValue::from_str("white"));
+                context.set_fill_style_str("white");
             },
         }
         context.arc(x, y, square_size / 2.0 - 5.0, 0.0, 2.0 * std::f64::consts::PI).unwrap();
@@ -61,22 +58,22 @@ impl Component for PieceView {
         context.stroke();
         match ctx.props().color {
             Color::Black => {
-                context.set_fill_style(&JsValue::from_str("white"));
+                context.set_fill_style_str("white");
             },
             Color::White => {
-                context.set_fill_style(&JsValue::from_str("black"));
+                context.set_fill_style_str("black");
             },
         }
         
         context.set_text_align("center");
         context.set_text_baseline("middle");
-        context.set_font(&format!("bold 25px serif"));
-        context.fill_text(&ctx.props().value.to_string(), x, y);
-        context.set_fill_style(&JsValue::from_str("black"));
+        context.set_font("bold 25px serif");
+        context.fill_text(&ctx.props().value.to_string(), x, y).unwrap();
+        context.set_fill_style_str("black");
         context.set_text_align("left");
         context.set_text_baseline("top");
-        context.set_font(&format!("bold 20px serif"));
-        context.fill_text(&format!("×{}", &ctx.props().num.to_string()), x+20.0, y+2.0);
+        context.set_font("bold 20px serif");
+        context.fill_text(&format!("×{}", &ctx.props().num.to_string()), x+20.0, y+2.0).unwrap();
     }
 
     fn update(&mut self, _ctx: &Context<Self>, _msg: Self::Message) -> bool {
@@ -0,0 +1,43 @@
+use yew::prelude::*;
+use yew::{Callback, function_component, html, Html, Properties};
+use web_sys::HtmlTextAreaElement;
+
+#[derive(Properties, PartialEq)]
+pub struct RecordViewProps {
+    /// The record of the game so far, written in the record format.
+    pub record: String,
+    /// The error of the last record that failed to load.
+    #[prop_or_default]
+    pub error: Option<String>,
+    pub on_load: Callback<String>,
+}
+
+#[function_component(RecordView)]
+pub fn record_view(props: &RecordViewProps) -> Html {
+    let text_area = use_node_ref();
+
+    let load = {
+        let text_area = text_area.clone();
+        let on_load = props.on_load.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            if let Some(text_area) = text_area.cast::<HtmlTextAreaElement>() {
+                on_load.emit(text_area.value())
+            }
+        })
+    };
+
+    html! {
+        <details class="mt-2">
+            <summary>{"Game Record"}</summary>
+            <textarea ref={text_area} rows="16" cols="60" value={props.record.clone()} />
+            <div>
+                <button type="submit" onclick={load}>{"Load"}</button>
+            </div>
+            if let Some(error) = &props.error {
+                <div class="alert alert-danger mt-2">{error}</div>
+            }
+        </details>
+    }
+}
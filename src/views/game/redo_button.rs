@@ -0,0 +1,24 @@
+use yew::prelude::*;
+use yew::{Callback, function_component, html, Html, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct RedoProps {
+    pub disabled: bool,
+    pub on_redo: Callback<()>
+}
+
+#[function_component(RedoButton)]
+pub fn redo_button(props: &RedoProps) -> Html {
+    let redo = {
+        let on_redo = props.on_redo.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_redo.emit(())
+        })
+    };
+
+    html! {
+        <button type="submit" disabled={props.disabled} onclick={redo}>{"Redo"}</button>
+    }
+}
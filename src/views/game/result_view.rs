@@ -0,0 +1,33 @@
+use yew::{function_component, html, Html, Properties};
+
+use crate::game::{assets::player::Players, rule::{color::Color, result::{EndReason, GameResult}}};
+
+#[derive(Properties, PartialEq)]
+pub struct ResultViewProps {
+    pub result: GameResult,
+    pub players: Players,
+}
+
+#[function_component(ResultView)]
+pub fn result_view(props: &ResultViewProps) -> Html {
+    let result = props.result;
+    let winner = match result.winner {
+        Some(Color::Black) => "Black wins!",
+        Some(Color::White) => "White wins!",
+        None => "Draw",
+    };
+    let reason = match result.reason {
+        EndReason::BoardFull => "The board is full.".to_string(),
+        EndReason::NoMoves => "Neither player can put a piece.".to_string(),
+        EndReason::PiecesExhausted(color) => format!("{:?} has no pieces left.", color),
+    };
+
+    html! {
+        <div class="alert alert-info mt-2">
+            <h4 class="alert-heading">{winner}</h4>
+            <p class="mb-0">{format!("Black: {} / White: {}", result.black_score, result.white_score)}</p>
+            <p class="mb-0">{reason}</p>
+            <p class="mb-0">{format!("Black: {} / White: {}", props.players.black, props.players.white)}</p>
+        </div>
+    }
+}
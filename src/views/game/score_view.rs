@@ -7,19 +7,16 @@ use crate::game::rule::score::Score;
 pub struct ScoreView {
     canvas: Option<HtmlCanvasElement>,
     context: Option<CanvasRenderingContext2d>,
-    score: Score,
 }
 
 impl Component for ScoreView {
     type Message = ();
     type Properties = Score;
 
-    fn create(ctx: &Context<Self>) -> Self {
-        let score = ctx.props();
+    fn create(_ctx: &Context<Self>) -> Self {
         Self {
             canvas: None,
             context: None,
-            score: *score,
         }
     }
 
@@ -35,11 +32,11 @@ impl Component for ScoreView {
         context.clear_rect(0.0, 0.0, 450.0, 100.0);
         context.set_text_align("center");
         context.set_text_baseline("middle");
-        context.set_font(&format!("bold 25px serif"));
-        context.fill_text(&format!("Score"), 50.0, 30.0);
-        context.set_font(&format!("bold 20px serif"));
-        context.fill_text(&format!("Black:{:?}", &ctx.props().black), 70.0, 55.0);
-        context.fill_text(&format!("White:{:?}", &ctx.props().white), 70.0, 80.0);
+        context.set_font("bold 25px serif");
+        context.fill_text("Score", 50.0, 30.0).unwrap();
+        context.set_font("bold 20px serif");
+        context.fill_text(&format!("Black:{:?}", &ctx.props().black), 70.0, 55.0).unwrap();
+        context.fill_text(&format!("White:{:?}", &ctx.props().white), 70.0, 80.0).unwrap();
     }
 
     fn view(&self, _ctx: &Context<Self>) -> Html {
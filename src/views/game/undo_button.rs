@@ -0,0 +1,24 @@
+use yew::prelude::*;
+use yew::{Callback, function_component, html, Html, Properties};
+
+#[derive(Properties, PartialEq)]
+pub struct UndoProps {
+    pub disabled: bool,
+    pub on_undo: Callback<()>
+}
+
+#[function_component(UndoButton)]
+pub fn undo_button(props: &UndoProps) -> Html {
+    let undo = {
+        let on_undo = props.on_undo.clone();
+
+        Callback::from(move |e: MouseEvent| {
+            e.prevent_default();
+            on_undo.emit(())
+        })
+    };
+
+    html! {
+        <button type="submit" disabled={props.disabled} onclick={undo}>{"Undo"}</button>
+    }
+}